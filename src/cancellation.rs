@@ -0,0 +1,84 @@
+use std::fmt::{Debug, Display, Formatter};
+#[cfg(feature = "async")]
+use std::future::poll_fn;
+#[cfg(feature = "async")]
+use std::pin::{Pin, pin};
+#[cfg(feature = "async")]
+use std::task::Poll;
+
+/// Outcome of a retry loop which was cancelled before it could finish on its own.
+///
+/// Distinguishes a cancelled retry loop from an exhausted one, which returns the last output of the operation as is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cancelled<Out> {
+    /// The number of attempts started, including an attempt interrupted by the cancellation.
+    pub attempts: usize,
+    /// The output of the last completed attempt, `None` if no attempt completed.
+    pub last_output: Option<Out>,
+}
+
+impl<Out> Display for Cancelled<Out> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "retry loop was cancelled after {} attempts",
+            self.attempts
+        )
+    }
+}
+
+impl<Out: Debug> std::error::Error for Cancelled<Out> {}
+
+/// A signal cancelling an asynchronous retry loop once it completes, see `retry_async_with_cancellation`.
+///
+/// Any future can be used as a signal, e.g. `CancellationToken::cancelled()` of `tokio-util`, a `oneshot` receiver
+/// or a timeout. By default, only delays are interrupted, a running attempt is awaited before the loop is cancelled.
+///
+/// ```rust
+/// use try_again::CancelSignal;
+///
+/// let shutdown = std::future::pending::<()>();
+/// let cancel = CancelSignal::on(shutdown).interrupting_attempts();
+/// ```
+#[derive(Debug)]
+#[cfg(feature = "async")]
+pub struct CancelSignal<Signal> {
+    pub(crate) signal: Signal,
+    pub(crate) interrupt_attempts: bool,
+}
+
+#[cfg(feature = "async")]
+impl<Signal: Future<Output = ()>> CancelSignal<Signal> {
+    /// Cancels the retry loop once `signal` completes.
+    pub fn on(signal: Signal) -> Self {
+        Self {
+            signal,
+            interrupt_attempts: false,
+        }
+    }
+
+    /// Also interrupts a running attempt by dropping its future, instead of awaiting it.
+    pub fn interrupting_attempts(mut self) -> Self {
+        self.interrupt_attempts = true;
+        self
+    }
+}
+
+/// Awaits `future`, unless `signal` completes first, in which case `None` is returned.
+///
+/// `signal` is polled first, so an already completed signal cancels even a `future` completing on its first poll.
+/// `signal` must not be polled again after it completed.
+#[cfg(feature = "async")]
+pub(crate) async fn until_cancelled<T>(
+    future: impl Future<Output = T>,
+    mut signal: Pin<&mut impl Future<Output = ()>>,
+) -> Option<T> {
+    let mut future = pin!(future);
+    poll_fn(|cx| {
+        if signal.as_mut().poll(cx).is_ready() {
+            return Poll::Ready(None);
+        }
+        future.as_mut().poll(cx).map(Some)
+    })
+    .await
+}
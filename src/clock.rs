@@ -0,0 +1,153 @@
+//! Sources of the current time, used by the retry loops to measure elapsed time (e.g. the `elapsed_ms` of an attempt
+//! and the `try_again_elapsed_seconds` metric).
+//!
+//! Reading `Instant::now()` directly makes time-based behavior untestable. Retry loops therefore read the time through
+//! the `Clock` of their options (see `RetryOptionsBuilder::clock`), which defaults to the `SystemClock`.
+//!
+//! - `SystemClock` reads the system's monotonic clock.
+//! - `TokioClock` reads tokio's clock, which honors `tokio::time::pause` (feature `async-tokio`).
+//! - `ManualClock` only moves when advanced explicitly, making tests deterministic.
+
+use crate::StdDuration;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// A source of the current time.
+pub trait Clock: Debug + Send + Sync {
+    fn now(&self) -> Instant;
+
+    /// The time passed since `earlier`, saturating at zero.
+    fn elapsed_since(&self, earlier: Instant) -> StdDuration {
+        self.now().saturating_duration_since(earlier)
+    }
+}
+
+/// A type-erased clock, e.g. stored in the retry options.
+pub type BoxedClock = Box<dyn Clock>;
+
+impl<C: Clock + ?Sized> Clock for Box<C> {
+    fn now(&self) -> Instant {
+        (**self).now()
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for Arc<C> {
+    fn now(&self) -> Instant {
+        (**self).now()
+    }
+}
+
+/// Reads the system's monotonic clock through `Instant::now()`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Reads tokio's clock through `tokio::time::Instant::now()`.
+///
+/// While the clock of the current runtime is paused (`tokio::time::pause`, requires tokio's `test-util` feature), time
+/// only advances when tokio auto-advances it or through `tokio::time::advance`.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg(feature = "async-tokio")]
+pub struct TokioClock;
+
+#[cfg(feature = "async-tokio")]
+impl Clock for TokioClock {
+    fn now(&self) -> Instant {
+        tokio::time::Instant::now().into_std()
+    }
+}
+
+/// A clock which only moves when advanced explicitly.
+///
+/// Clones share the same time, so a clone can be handed to the retry loop while the original is advanced by the test.
+///
+/// ```rust
+/// use try_again::IntoStdDuration;
+/// use try_again::clock::{Clock, ManualClock};
+///
+/// let clock = ManualClock::new();
+/// let start = clock.now();
+///
+/// clock.advance(5.secs());
+///
+/// assert_eq!(clock.elapsed_since(start), 5.secs());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    now: Arc<Mutex<Instant>>,
+}
+
+impl ManualClock {
+    /// Creates a clock starting at the current system time.
+    pub fn new() -> Self {
+        Self {
+            now: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    /// Moves the clock forward by `by`.
+    pub fn advance(&self, by: StdDuration) {
+        let mut now = self
+            .now
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *now += by;
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        *self
+            .now
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::IntoStdDuration;
+    use assertr::prelude::*;
+
+    #[test]
+    fn manual_clock_only_moves_when_advanced() {
+        let clock = ManualClock::new();
+        let start = clock.now();
+
+        clock.clone().advance(100.millis());
+
+        assert_that(clock.elapsed_since(start)).is_equal_to(100.millis());
+        assert_that(clock.now()).is_equal_to(start + 100.millis());
+    }
+
+    #[test]
+    fn elapsed_since_saturates_at_zero() {
+        let clock = ManualClock::new();
+        let later = clock.now() + 1.secs();
+
+        assert_that(clock.elapsed_since(later)).is_equal_to(StdDuration::ZERO);
+    }
+
+    #[cfg(feature = "async-tokio")]
+    #[tokio::test(start_paused = true)]
+    async fn tokio_clock_honors_paused_time() {
+        let start = TokioClock.now();
+
+        tokio::time::advance(10.secs()).await;
+
+        assert_that(TokioClock.elapsed_since(start)).is_equal_to(10.secs());
+    }
+}
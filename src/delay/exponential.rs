@@ -1,15 +1,22 @@
 use crate::StdDuration;
+use crate::delay::InvalidSchedule;
+use crate::delay::validation::validate_exponential_backoff;
+use crate::duration::ToStdDuration;
+use crate::duration::human::format_duration;
 use crate::tracked_iterator::{FiniteIterator, IntoTrackedIterator};
+use std::fmt::{Display, Formatter};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExponentialBackoff {
+    #[cfg_attr(feature = "serde", serde(with = "crate::duration::human::string"))]
     pub initial_delay: StdDuration,
 }
 
 impl ExponentialBackoff {
-    pub fn of_initial_delay(initial_delay: impl Into<StdDuration>) -> Self {
+    pub fn of_initial_delay(initial_delay: impl ToStdDuration) -> Self {
         Self {
-            initial_delay: initial_delay.into(),
+            initial_delay: initial_delay.to_std_duration(),
         }
     }
 
@@ -18,34 +25,132 @@ impl ExponentialBackoff {
             initial_delay: self.initial_delay,
             last_delay: StdDuration::ZERO,
             max_delay: None,
+            factor: ExponentialBackoffWithCap::DEFAULT_FACTOR,
             first: true,
         }
     }
 
-    pub fn capped_at(self, max_delay: impl Into<StdDuration>) -> ExponentialBackoffWithCap {
+    /// Like `uncapped`, but rejects configurations which are most likely unintended (see `InvalidSchedule`).
+    pub fn try_uncapped(self) -> Result<ExponentialBackoffWithCap, InvalidSchedule> {
+        let backoff = self.uncapped();
+        backoff.validate().map(|()| backoff)
+    }
+
+    /// Like `capped_at`, but rejects configurations which are most likely unintended (see `InvalidSchedule`),
+    /// e.g. a `max_delay` below the initial delay.
+    pub fn try_capped_at(
+        self,
+        max_delay: impl ToStdDuration,
+    ) -> Result<ExponentialBackoffWithCap, InvalidSchedule> {
+        let backoff = self.capped_at(max_delay);
+        backoff.validate().map(|()| backoff)
+    }
+
+    pub fn capped_at(self, max_delay: impl ToStdDuration) -> ExponentialBackoffWithCap {
         ExponentialBackoffWithCap {
             initial_delay: self.initial_delay,
             last_delay: StdDuration::ZERO,
-            max_delay: Some(max_delay.into()),
+            max_delay: Some(max_delay.to_std_duration()),
+            factor: ExponentialBackoffWithCap::DEFAULT_FACTOR,
             first: true,
         }
     }
 }
 
+impl Display for ExponentialBackoff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "exponential backoff starting at {}",
+            format_duration(self.initial_delay)
+        )
+    }
+}
+
+/// An exponential backoff, created through `ExponentialBackoff::uncapped` or `ExponentialBackoff::capped_at`.
+///
+/// The `factor` is private and configured through `with_factor`, so this struct can no longer be created from a struct
+/// literal.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExponentialBackoffWithCap {
+    #[cfg_attr(feature = "serde", serde(with = "crate::duration::human::string"))]
     pub initial_delay: StdDuration,
+    /// Iteration state, not part of the serialized representation.
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub last_delay: StdDuration,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "crate::duration::human::string::option")
+    )]
     pub max_delay: Option<StdDuration>,
+    /// The delay is multiplied by this factor on every retry, see `with_factor`.
+    #[cfg_attr(feature = "serde", serde(default = "default_factor"))]
+    factor: u32,
+    /// Iteration state, not part of the serialized representation.
+    #[cfg_attr(feature = "serde", serde(skip, default = "default_first"))]
     pub first: bool,
 }
 
+#[cfg(feature = "serde")]
+pub(crate) fn default_factor() -> u32 {
+    ExponentialBackoffWithCap::DEFAULT_FACTOR
+}
+
+#[cfg(feature = "serde")]
+pub(crate) fn is_default_factor(factor: &u32) -> bool {
+    *factor == ExponentialBackoffWithCap::DEFAULT_FACTOR
+}
+
+#[cfg(feature = "serde")]
+fn default_first() -> bool {
+    true
+}
+
 impl ExponentialBackoffWithCap {
+    pub const DEFAULT_FACTOR: u32 = 2;
+
+    /// Multiplies the delay by `factor` (instead of 2) on every retry.
+    pub fn with_factor(mut self, factor: u32) -> Self {
+        self.factor = factor;
+        self
+    }
+
+    /// The factor the delay is multiplied by on every retry.
+    pub fn factor(&self) -> u32 {
+        self.factor
+    }
+
+    /// Checks for configurations which are most likely unintended: A zero initial delay, a factor below 2 or a
+    /// `max_delay` below the initial delay.
+    pub fn validate(&self) -> Result<(), InvalidSchedule> {
+        validate_exponential_backoff(self.initial_delay, self.max_delay, self.factor)
+    }
+
     pub fn take(self, count: usize) -> FiniteIterator<std::iter::Take<ExponentialBackoffWithCap>> {
         self.into_tracked().take(count)
     }
 }
 
+impl Display for ExponentialBackoffWithCap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "exponential backoff starting at {}, ",
+            format_duration(self.initial_delay)
+        )?;
+        match self.factor {
+            2 => f.write_str("doubling on every retry")?,
+            3 => f.write_str("tripling on every retry")?,
+            factor => write!(f, "multiplied by {factor} on every retry")?,
+        }
+        match self.max_delay {
+            Some(max_delay) => write!(f, ", capped at {}", format_duration(max_delay)),
+            Option::None => f.write_str(", uncapped"),
+        }
+    }
+}
+
 impl Iterator for ExponentialBackoffWithCap {
     type Item = StdDuration;
 
@@ -56,7 +161,10 @@ impl Iterator for ExponentialBackoffWithCap {
             return Some(self.initial_delay);
         }
 
-        let mut next = self.last_delay * 2;
+        let mut next = self
+            .last_delay
+            .checked_mul(self.factor)
+            .unwrap_or(StdDuration::MAX);
         if let Some(max_delay) = self.max_delay {
             if next > max_delay {
                 next = max_delay;
@@ -115,4 +223,78 @@ mod test {
             .is_equal_to(250.millis());
         assert_that(delay.next()).is_none();
     }
+
+    #[test]
+    fn exponential_backoff_delay_strategy_multiplies_the_delay_by_the_configured_factor() {
+        let mut delay = ExponentialBackoff::of_initial_delay(10.millis())
+            .uncapped()
+            .with_factor(3)
+            .take(3);
+
+        assert_that(delay.next()).is_some().is_equal_to(10.millis());
+        assert_that(delay.next()).is_some().is_equal_to(30.millis());
+        assert_that(delay.next()).is_some().is_equal_to(90.millis());
+        assert_that(delay.next()).is_none();
+    }
+
+    #[test]
+    fn validation_rejects_backoffs_which_do_not_grow_as_intended() {
+        assert_that(
+            ExponentialBackoff::of_initial_delay(5.secs())
+                .try_capped_at(1.secs())
+                .expect_err("cap below initial delay"),
+        )
+        .is_equal_to(InvalidSchedule::CapBelowInitialDelay {
+            initial_delay: 5.secs(),
+            max_delay: 1.secs(),
+        });
+        assert_that(
+            ExponentialBackoff::of_initial_delay(StdDuration::ZERO)
+                .try_uncapped()
+                .expect_err("zero initial delay"),
+        )
+        .is_equal_to(InvalidSchedule::ZeroInitialDelay);
+        assert_that(
+            ExponentialBackoff::of_initial_delay(1.secs())
+                .uncapped()
+                .with_factor(1)
+                .validate(),
+        )
+        .is_equal_to(Err(InvalidSchedule::NoGrowth { factor: 1 }));
+        assert_that(
+            ExponentialBackoff::of_initial_delay(1.secs())
+                .try_capped_at(1.secs())
+                .is_ok(),
+        )
+        .is_true();
+    }
+
+    #[test]
+    fn exponential_backoff_is_described_in_words() {
+        let backoff = ExponentialBackoff::of_initial_delay(100.millis());
+
+        assert_that(backoff.capped_at(5.secs()).to_string()).is_equal_to(
+            "exponential backoff starting at 100ms, doubling on every retry, capped at 5s"
+                .to_owned(),
+        );
+        assert_that(backoff.uncapped().with_factor(4).to_string()).is_equal_to(
+            "exponential backoff starting at 100ms, multiplied by 4 on every retry, uncapped"
+                .to_owned(),
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serializes_the_configuration_without_the_iteration_state() {
+        let mut backoff = ExponentialBackoff::of_initial_delay(100.millis()).capped_at(1.secs());
+        backoff.next();
+        let json = r#"{"initial_delay":"100ms","max_delay":"1s","factor":2}"#;
+
+        assert_that(serde_json::to_string(&backoff).expect("serializable"))
+            .is_equal_to(json.to_owned());
+
+        let mut deserialized =
+            serde_json::from_str::<ExponentialBackoffWithCap>(json).expect("deserializable");
+        assert_that(deserialized.next()).is_equal_to(Some(100.millis()));
+    }
 }
@@ -1,15 +1,20 @@
 use crate::StdDuration;
+use crate::duration::ToStdDuration;
+use crate::duration::human::format_duration;
 use crate::tracked_iterator::{FiniteIterator, IntoTrackedIterator};
+use std::fmt::{Display, Formatter};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Fixed {
+    #[cfg_attr(feature = "serde", serde(with = "crate::duration::human::string"))]
     pub delay: StdDuration,
 }
 
 impl Fixed {
-    pub fn of(delay: impl Into<StdDuration>) -> Self {
+    pub fn of(delay: impl ToStdDuration) -> Self {
         Self {
-            delay: delay.into(),
+            delay: delay.to_std_duration(),
         }
     }
 
@@ -26,6 +31,12 @@ impl Iterator for Fixed {
     }
 }
 
+impl Display for Fixed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "fixed delay of {}", format_duration(self.delay))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -41,4 +52,10 @@ mod test {
         assert_that(delay.next()).is_some().is_equal_to(50.millis());
         assert_that(delay.next()).is_none();
     }
+
+    #[test]
+    fn is_described_in_words() {
+        assert_that(Fixed::of(1500.millis()).to_string())
+            .is_equal_to("fixed delay of 1500ms".to_owned());
+    }
 }
@@ -0,0 +1,143 @@
+use crate::StdDuration;
+use crate::delay_strategy::DelayStrategy;
+use std::fmt::{Display, Formatter};
+use std::hash::{BuildHasher, Hasher};
+
+/// Randomization applied to the delays of a strategy, spreading out retries of concurrently failing callers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Jitter {
+    /// Delays are used as is.
+    #[default]
+    None,
+
+    /// Delays are chosen uniformly from `[0, delay]`.
+    Full,
+
+    /// Delays are chosen uniformly from `[delay / 2, delay]`.
+    Equal,
+}
+
+impl Jitter {
+    /// The smallest and largest delay this jitter may turn `delay` into.
+    pub fn bounds(self, delay: StdDuration) -> (StdDuration, StdDuration) {
+        match self {
+            Jitter::None => (delay, delay),
+            Jitter::Full => (StdDuration::ZERO, delay),
+            Jitter::Equal => (delay / 2, delay),
+        }
+    }
+
+    fn apply(self, delay: StdDuration, rng: &mut Rng) -> StdDuration {
+        let (min, max) = self.bounds(delay);
+        if min == max {
+            return min;
+        }
+        min + (max - min).mul_f64(rng.next_f64())
+    }
+}
+
+impl Display for Jitter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Jitter::None => "no jitter",
+            Jitter::Full => "full jitter",
+            Jitter::Equal => "equal jitter",
+        })
+    }
+}
+
+/// Applies `Jitter` to the delays of another strategy.
+#[derive(Debug, Clone)]
+pub struct Jittered<S> {
+    strategy: S,
+    jitter: Jitter,
+    rng: Rng,
+}
+
+impl<S> Jittered<S> {
+    pub fn new(strategy: S, jitter: Jitter) -> Self {
+        Self {
+            strategy,
+            jitter,
+            rng: Rng::from_entropy(),
+        }
+    }
+}
+
+impl<S: DelayStrategy<StdDuration>> DelayStrategy<StdDuration> for Jittered<S> {
+    fn next_delay(&mut self) -> Option<StdDuration> {
+        self.strategy
+            .next_delay()
+            .map(|delay| self.jitter.apply(delay, &mut self.rng))
+    }
+
+    fn next_delay_bounds(&mut self) -> Option<(StdDuration, StdDuration)> {
+        self.strategy
+            .next_delay_bounds()
+            .map(|(min, max)| (self.jitter.bounds(min).0, self.jitter.bounds(max).1))
+    }
+}
+
+impl<S: Display> Display for Jittered<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} with {}", self.strategy, self.jitter)
+    }
+}
+
+/// Minimal xorshift64* generator. Jitter only needs cheap, roughly uniform values, not cryptographic quality.
+#[derive(Debug, Clone)]
+struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    fn from_entropy() -> Self {
+        let seed = std::collections::hash_map::RandomState::new()
+            .build_hasher()
+            .finish();
+        Self { state: seed | 1 }
+    }
+
+    fn next_f64(&mut self) -> f64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        let value = self.state.wrapping_mul(0x2545_f491_4f6c_dd1d);
+        (value >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::IntoStdDuration;
+    use crate::delay::Fixed;
+    use assertr::prelude::*;
+
+    #[test]
+    fn jittered_delays_stay_within_the_bounds_of_the_jitter() {
+        for jitter in [Jitter::None, Jitter::Full, Jitter::Equal] {
+            let (min, max) = jitter.bounds(100.millis());
+            let mut strategy = Jittered::new(Fixed::of(100.millis()).take(100), jitter);
+
+            while let Some(delay) = strategy.next_delay() {
+                assert_that(delay).is_greater_or_equal_to(min);
+                assert_that(delay).is_less_or_equal_to(max);
+            }
+        }
+    }
+
+    #[test]
+    fn jittered_strategy_ends_with_the_wrapped_strategy() {
+        let mut strategy = Jittered::new(Fixed::of(1.millis()).take(2), Jitter::Full);
+
+        assert_that(strategy.next_delay()).is_some();
+        assert_that(strategy.next_delay()).is_some();
+        assert_that(strategy.next_delay()).is_none();
+    }
+}
@@ -1,7 +1,18 @@
 mod exponential;
 mod fixed;
+mod jitter;
 mod none;
+mod preview;
+mod schedule;
+mod spec;
+mod validation;
 
 pub use exponential::ExponentialBackoff;
+pub use exponential::ExponentialBackoffWithCap;
 pub use fixed::Fixed;
+pub use jitter::{Jitter, Jittered};
 pub use none::None;
+pub use preview::{Preview, PreviewedRetry};
+pub use schedule::{Schedule, ScheduleStrategy};
+pub use spec::{ParseScheduleError, ScheduleSpec, parse_strategy};
+pub use validation::InvalidSchedule;
@@ -1,7 +1,9 @@
 use crate::StdDuration;
 use crate::tracked_iterator::{FiniteIterator, IntoTrackedIterator};
+use std::fmt::{Display, Formatter};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct None;
 
 impl None {
@@ -18,6 +20,12 @@ impl Iterator for None {
     }
 }
 
+impl Display for None {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("no delay")
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -0,0 +1,187 @@
+use crate::StdDuration;
+use crate::delay_strategy::DelayStrategy;
+use crate::duration::ToStdDuration;
+use crate::duration::human::format_duration;
+use std::fmt::{Display, Formatter};
+
+/// The delays a strategy would apply, computed up front without sleeping. Created through `DelayStrategy::preview`.
+///
+/// For jittered strategies, every delay (and therefore every offset) is given as the range the actual value is chosen
+/// from. Offsets only account for the delays, not for the time spent running the operation.
+///
+/// At most `MAX_RETRIES` retries are previewed, so strategies with (practically) unlimited retries can be previewed as
+/// well. Totals of a truncated preview only cover the previewed retries.
+///
+/// Displayed like `attempts at t=0, 100ms, 300ms, 700ms; worst-case total 700ms`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Preview {
+    retries: Vec<PreviewedRetry>,
+    truncated: bool,
+}
+
+/// A single retry of a `Preview`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PreviewedRetry {
+    /// The smallest delay applied before this retry.
+    pub min_delay: StdDuration,
+    /// The largest delay applied before this retry.
+    pub max_delay: StdDuration,
+    /// The earliest point in time, relative to the start of the initial attempt, at which this retry starts.
+    pub min_offset: StdDuration,
+    /// The latest point in time, relative to the start of the initial attempt, at which this retry starts.
+    pub max_offset: StdDuration,
+}
+
+impl PreviewedRetry {
+    /// Whether the delay of this retry is randomized.
+    pub fn is_jittered(&self) -> bool {
+        self.min_delay != self.max_delay
+    }
+}
+
+impl Preview {
+    /// The maximum number of retries a preview computes.
+    pub const MAX_RETRIES: usize = 1000;
+
+    pub(crate) fn of<Delay, S>(mut strategy: S) -> Self
+    where
+        Delay: Clone + ToStdDuration,
+        S: DelayStrategy<Delay>,
+    {
+        let mut retries = Vec::new();
+        let (mut min_offset, mut max_offset) = (StdDuration::ZERO, StdDuration::ZERO);
+        while let Some((min_delay, max_delay)) = strategy.next_delay_bounds() {
+            if retries.len() == Self::MAX_RETRIES {
+                return Self {
+                    retries,
+                    truncated: true,
+                };
+            }
+            let (min_delay, max_delay) = (min_delay.to_std_duration(), max_delay.to_std_duration());
+            min_offset = min_offset.saturating_add(min_delay);
+            max_offset = max_offset.saturating_add(max_delay);
+            retries.push(PreviewedRetry {
+                min_delay,
+                max_delay,
+                min_offset,
+                max_offset,
+            });
+        }
+        Self {
+            retries,
+            truncated: false,
+        }
+    }
+
+    /// Whether the strategy has more than `MAX_RETRIES` retries, which were left out of this preview.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// The retries following the initial attempt.
+    pub fn retries(&self) -> &[PreviewedRetry] {
+        &self.retries
+    }
+
+    /// The total number of attempts, including the initial one.
+    pub fn attempts(&self) -> usize {
+        self.retries.len() + 1
+    }
+
+    /// The shortest possible total delay until the last retry starts.
+    pub fn best_case_total(&self) -> StdDuration {
+        self.retries
+            .last()
+            .map_or(StdDuration::ZERO, |retry| retry.min_offset)
+    }
+
+    /// The longest possible total delay until the last retry starts.
+    pub fn worst_case_total(&self) -> StdDuration {
+        self.retries
+            .last()
+            .map_or(StdDuration::ZERO, |retry| retry.max_offset)
+    }
+}
+
+impl Display for Preview {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("attempts at t=0")?;
+        for retry in &self.retries {
+            match retry.min_offset == retry.max_offset {
+                true => write!(f, ", {}", format_duration(retry.max_offset))?,
+                false => write!(
+                    f,
+                    ", {}..{}",
+                    format_duration(retry.min_offset),
+                    format_duration(retry.max_offset)
+                )?,
+            }
+        }
+        if self.truncated {
+            f.write_str(", ...")?;
+        }
+        write!(
+            f,
+            "; worst-case total {}{}",
+            if self.truncated { "at least " } else { "" },
+            format_duration(self.worst_case_total())
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::IntoStdDuration;
+    use crate::delay::{ExponentialBackoff, Fixed, Jitter, Jittered};
+    use assertr::prelude::*;
+
+    #[test]
+    fn preview_accumulates_the_delays_of_the_strategy() {
+        let preview = ExponentialBackoff::of_initial_delay(100.millis())
+            .uncapped()
+            .take(3)
+            .preview();
+
+        assert_that(preview.attempts()).is_equal_to(4);
+        assert_that(preview.worst_case_total()).is_equal_to(700.millis());
+        assert_that(preview.to_string())
+            .is_equal_to("attempts at t=0, 100ms, 300ms, 700ms; worst-case total 700ms".to_owned());
+    }
+
+    #[test]
+    fn preview_of_jittered_strategies_reports_the_possible_range() {
+        let preview = Jittered::new(Fixed::of(100.millis()).take(2), Jitter::Equal).preview();
+
+        assert_that(preview.retries()[1]).is_equal_to(PreviewedRetry {
+            min_delay: 50.millis(),
+            max_delay: 100.millis(),
+            min_offset: 100.millis(),
+            max_offset: 200.millis(),
+        });
+        assert_that(preview.best_case_total()).is_equal_to(100.millis());
+        assert_that(preview.to_string()).is_equal_to(
+            "attempts at t=0, 50ms..100ms, 100ms..200ms; worst-case total 200ms".to_owned(),
+        );
+    }
+
+    #[test]
+    fn preview_of_practically_unlimited_strategies_is_truncated() {
+        let preview = crate::delay::Schedule::None {
+            retries: usize::MAX,
+        }
+        .preview();
+
+        assert_that(preview.is_truncated()).is_true();
+        assert_that(preview.attempts()).is_equal_to(Preview::MAX_RETRIES + 1);
+        assert_that(preview.to_string()).ends_with(", 0s, ...; worst-case total at least 0s");
+    }
+
+    #[test]
+    fn preview_does_not_consume_the_strategy() {
+        let mut strategy = Fixed::of(1.millis()).take(1);
+        let _ = strategy.preview();
+
+        assert_that(strategy.next_delay()).is_some();
+    }
+}
@@ -0,0 +1,543 @@
+use crate::StdDuration;
+use crate::delay::validation::{
+    total_delay, validate_exponential_backoff, validate_worst_case_total,
+};
+use crate::delay::{ExponentialBackoff, ExponentialBackoffWithCap, Fixed, Jitter, Jittered, None};
+use crate::delay::{InvalidSchedule, Preview};
+use crate::delay_strategy::DelayStrategy;
+use crate::duration::human::format_duration;
+use crate::tracked_iterator::FiniteIterator;
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use std::iter::Take;
+
+/// Description of a finite delay strategy.
+///
+/// In contrast to the strategies themselves, which are consumed when used, a `Schedule` is a plain value that can be
+/// stored anywhere (even in a `static`) and hands out a fresh strategy through `strategy()` whenever needed.
+///
+/// Schedules can also be parsed from a compact textual notation, see `ScheduleSpec`.
+///
+/// With the `serde` feature, schedules are (de)serialized as internally tagged values using human-friendly durations,
+/// e.g. `{ "type": "exponential_backoff", "initial_delay": "100ms", "max_delay": "5s", "retries": 8 }`. Sequences list
+/// their segments, e.g. `{ "type": "sequence", "segments": [{ "type": "fixed", "delay": "1s", "retries": 2 }, ..] }`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)
+)]
+pub enum Schedule {
+    /// Retry up to `retries` times without any delay.
+    None { retries: usize },
+
+    /// Retry up to `retries` times, always waiting for `delay`, randomized by `jitter`.
+    Fixed {
+        #[cfg_attr(feature = "serde", serde(with = "crate::duration::human::string"))]
+        delay: StdDuration,
+        retries: usize,
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "is_no_jitter")
+        )]
+        jitter: Jitter,
+    },
+
+    /// Retry up to `retries` times, starting with `initial_delay` and multiplying the delay by `factor` on every retry,
+    /// optionally capped at `max_delay` and randomized by `jitter`. The `factor` is optional in the serialized
+    /// representation and defaults to 2.
+    ExponentialBackoff {
+        #[cfg_attr(feature = "serde", serde(with = "crate::duration::human::string"))]
+        initial_delay: StdDuration,
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                default,
+                skip_serializing_if = "Option::is_none",
+                with = "crate::duration::human::string::option"
+            )
+        )]
+        max_delay: Option<StdDuration>,
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                default = "crate::delay::exponential::default_factor",
+                skip_serializing_if = "crate::delay::exponential::is_default_factor"
+            )
+        )]
+        factor: u32,
+        retries: usize,
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "is_no_jitter")
+        )]
+        jitter: Jitter,
+    },
+
+    /// Retry according to each of the `segments` in turn, e.g. a few quick retries followed by a slower backoff.
+    ///
+    /// Segments can be borrowed from a `static` slice, so sequences can be constructed in `const` contexts as well.
+    Sequence { segments: Cow<'static, [Schedule]> },
+}
+
+#[cfg(feature = "serde")]
+fn is_no_jitter(jitter: &Jitter) -> bool {
+    *jitter == Jitter::None
+}
+
+impl Schedule {
+    /// The maximum number of retries (not counting the initial attempt).
+    pub fn retries(&self) -> usize {
+        match self {
+            Schedule::None { retries }
+            | Schedule::Fixed { retries, .. }
+            | Schedule::ExponentialBackoff { retries, .. } => *retries,
+            Schedule::Sequence { segments } => segments
+                .iter()
+                .map(Schedule::retries)
+                .fold(0, usize::saturating_add),
+        }
+    }
+
+    /// Checks for configurations which are most likely unintended (see `InvalidSchedule`), e.g. no retries at all or
+    /// an exponential backoff capped below its initial delay.
+    pub fn validate(&self) -> Result<(), InvalidSchedule> {
+        if self.retries() == 0 {
+            return Err(InvalidSchedule::NoRetries);
+        }
+        self.validate_segments()
+    }
+
+    /// Validates every segment on its own. Segments without retries are fine, as long as the schedule retries at all.
+    fn validate_segments(&self) -> Result<(), InvalidSchedule> {
+        match self {
+            Schedule::None { .. } | Schedule::Fixed { .. } => Ok(()),
+            Schedule::ExponentialBackoff {
+                initial_delay,
+                max_delay,
+                factor,
+                ..
+            } => validate_exponential_backoff(*initial_delay, *max_delay, *factor),
+            Schedule::Sequence { segments } => {
+                segments.iter().try_for_each(Schedule::validate_segments)
+            }
+        }
+    }
+
+    /// Like `validate`, additionally rejecting schedules whose delays may add up to more than `max_total_delay`.
+    pub fn validate_within(&self, max_total_delay: StdDuration) -> Result<(), InvalidSchedule> {
+        self.validate()?;
+        validate_worst_case_total(self.worst_case_total(), max_total_delay)
+    }
+
+    /// The largest possible sum of all delays of this schedule. Jitter never increases a delay, so this is the sum of
+    /// the unjittered delays.
+    pub fn worst_case_total(&self) -> StdDuration {
+        match self {
+            Schedule::None { .. } => StdDuration::ZERO,
+            Schedule::Fixed { delay, retries, .. } => {
+                total_delay(*delay, Some(*delay), 1, *retries)
+            }
+            Schedule::ExponentialBackoff {
+                initial_delay,
+                max_delay,
+                factor,
+                retries,
+                ..
+            } => total_delay(*initial_delay, *max_delay, *factor, *retries),
+            Schedule::Sequence { segments } => segments
+                .iter()
+                .map(Schedule::worst_case_total)
+                .fold(StdDuration::ZERO, StdDuration::saturating_add),
+        }
+    }
+
+    /// Computes all delays of this schedule, see `Preview`.
+    pub fn preview(&self) -> Preview {
+        self.strategy().preview()
+    }
+
+    /// Creates a fresh delay strategy following this schedule.
+    pub fn strategy(&self) -> ScheduleStrategy {
+        let inner = match self {
+            Schedule::None { retries } => Inner::None(None.take(*retries)),
+            Schedule::Fixed {
+                delay,
+                retries,
+                jitter,
+            } => Inner::Fixed(Jittered::new(Fixed::of(*delay).take(*retries), *jitter)),
+            Schedule::ExponentialBackoff {
+                initial_delay,
+                max_delay,
+                factor,
+                retries,
+                jitter,
+            } => {
+                let backoff = ExponentialBackoff::of_initial_delay(*initial_delay);
+                let backoff = match max_delay {
+                    Some(max_delay) => backoff.capped_at(*max_delay),
+                    Option::None => backoff.uncapped(),
+                };
+                Inner::ExponentialBackoff(Jittered::new(
+                    backoff.with_factor(*factor).take(*retries),
+                    *jitter,
+                ))
+            }
+            Schedule::Sequence { segments } => {
+                Inner::Sequence(segments.iter().map(Schedule::strategy).collect())
+            }
+        };
+        ScheduleStrategy {
+            schedule: self.clone(),
+            inner,
+        }
+    }
+}
+
+impl Display for Schedule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let jitter = match self {
+            Schedule::Sequence { segments } => {
+                for (i, segment) in segments.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", then ")?;
+                    }
+                    segment.fmt(f)?;
+                }
+                return match segments.is_empty() {
+                    true => f.write_str("no retries"),
+                    false => Ok(()),
+                };
+            }
+            Schedule::None { .. } => Jitter::None,
+            Schedule::Fixed { jitter, .. } | Schedule::ExponentialBackoff { jitter, .. } => *jitter,
+        };
+        match *self {
+            Schedule::None { .. } | Schedule::Sequence { .. } => f.write_str("no delay")?,
+            Schedule::Fixed { delay, .. } => write!(f, "{}", Fixed::of(delay))?,
+            Schedule::ExponentialBackoff {
+                initial_delay,
+                max_delay,
+                factor,
+                ..
+            } => {
+                write!(
+                    f,
+                    "exponential backoff starting at {}, ",
+                    format_duration(initial_delay)
+                )?;
+                match factor {
+                    2 => f.write_str("doubling on every retry")?,
+                    3 => f.write_str("tripling on every retry")?,
+                    factor => write!(f, "multiplied by {factor} on every retry")?,
+                }
+                if let Some(max_delay) = max_delay {
+                    write!(f, ", capped at {}", format_duration(max_delay))?;
+                }
+            }
+        }
+        if jitter != Jitter::None {
+            write!(f, " with {jitter}")?;
+        }
+        let retries = self.retries();
+        let plural = if retries == 1 { "retry" } else { "retries" };
+        write!(f, ", up to {retries} {plural}")
+    }
+}
+
+/// The delay strategy created by `Schedule::strategy`.
+///
+/// With the `serde` feature, it can be deserialized directly from the representation of a `Schedule`.
+#[derive(Debug, Clone)]
+pub struct ScheduleStrategy {
+    schedule: Schedule,
+    inner: Inner,
+}
+
+impl Display for ScheduleStrategy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.schedule.fmt(f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ScheduleStrategy {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Schedule::deserialize(deserializer).map(|schedule| schedule.strategy())
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Inner {
+    None(FiniteIterator<Take<None>>),
+    Fixed(Jittered<FiniteIterator<Take<Fixed>>>),
+    ExponentialBackoff(Jittered<FiniteIterator<Take<ExponentialBackoffWithCap>>>),
+    /// The strategies of the remaining segments, one after the other.
+    Sequence(VecDeque<ScheduleStrategy>),
+}
+
+impl DelayStrategy<StdDuration> for ScheduleStrategy {
+    fn next_delay(&mut self) -> Option<StdDuration> {
+        match &mut self.inner {
+            Inner::None(strategy) => strategy.next_delay(),
+            Inner::Fixed(strategy) => strategy.next_delay(),
+            Inner::ExponentialBackoff(strategy) => strategy.next_delay(),
+            Inner::Sequence(strategies) => next_of_sequence(strategies, |s| s.next_delay()),
+        }
+    }
+
+    fn next_delay_bounds(&mut self) -> Option<(StdDuration, StdDuration)> {
+        match &mut self.inner {
+            Inner::None(strategy) => strategy.next_delay_bounds(),
+            Inner::Fixed(strategy) => strategy.next_delay_bounds(),
+            Inner::ExponentialBackoff(strategy) => strategy.next_delay_bounds(),
+            Inner::Sequence(strategies) => next_of_sequence(strategies, |s| s.next_delay_bounds()),
+        }
+    }
+}
+
+/// Advances the first strategy of `strategies` which is not exhausted yet, dropping exhausted ones along the way.
+fn next_of_sequence<T>(
+    strategies: &mut VecDeque<ScheduleStrategy>,
+    mut next: impl FnMut(&mut ScheduleStrategy) -> Option<T>,
+) -> Option<T> {
+    while let Some(strategy) = strategies.front_mut() {
+        match next(strategy) {
+            Some(value) => return Some(value),
+            Option::None => {
+                strategies.pop_front();
+            }
+        }
+    }
+    Option::None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::IntoStdDuration;
+    use assertr::prelude::*;
+
+    fn delays(schedule: &Schedule) -> Vec<StdDuration> {
+        let mut strategy = schedule.strategy();
+        std::iter::from_fn(|| strategy.next_delay()).collect()
+    }
+
+    #[test]
+    fn each_strategy_starts_from_the_beginning_of_the_schedule() {
+        let schedule = Schedule::ExponentialBackoff {
+            initial_delay: 50.millis(),
+            max_delay: Some(150.millis()),
+            factor: 2,
+            retries: 4,
+            jitter: Jitter::None,
+        };
+
+        let expected = [50.millis(), 100.millis(), 150.millis(), 150.millis()];
+        assert_that(delays(&schedule)).contains_exactly(expected);
+        assert_that(delays(&schedule)).contains_exactly(expected);
+    }
+
+    #[test]
+    fn fixed_and_none_schedules_yield_the_configured_number_of_delays() {
+        assert_that(delays(&Schedule::Fixed {
+            delay: 10.millis(),
+            retries: 2,
+            jitter: Jitter::None,
+        }))
+        .contains_exactly([10.millis(), 10.millis()]);
+        assert_that(delays(&Schedule::None { retries: 1 })).contains_exactly([StdDuration::ZERO]);
+    }
+
+    #[test]
+    fn schedules_are_described_in_words_and_previewed() {
+        let schedule = Schedule::ExponentialBackoff {
+            initial_delay: 100.millis(),
+            max_delay: Some(400.millis()),
+            factor: 2,
+            retries: 4,
+            jitter: Jitter::None,
+        };
+
+        assert_that(schedule.to_string()).is_equal_to(
+            "exponential backoff starting at 100ms, doubling on every retry, capped at 400ms, up to 4 retries"
+                .to_owned(),
+        );
+        assert_that(schedule.preview().to_string()).is_equal_to(
+            "attempts at t=0, 100ms, 300ms, 700ms, 1100ms; worst-case total 1100ms".to_owned(),
+        );
+    }
+
+    #[test]
+    fn validation_rejects_unintended_schedules() {
+        assert_that(
+            Schedule::Fixed {
+                delay: 1.secs(),
+                retries: 0,
+                jitter: Jitter::None,
+            }
+            .validate(),
+        )
+        .is_equal_to(Err(InvalidSchedule::NoRetries));
+        assert_that(
+            Schedule::ExponentialBackoff {
+                initial_delay: 5.secs(),
+                max_delay: Some(1.secs()),
+                factor: 2,
+                retries: 3,
+                jitter: Jitter::None,
+            }
+            .validate(),
+        )
+        .is_equal_to(Err(InvalidSchedule::CapBelowInitialDelay {
+            initial_delay: 5.secs(),
+            max_delay: 1.secs(),
+        }));
+    }
+
+    #[test]
+    fn validation_within_a_limit_rejects_schedules_taking_too_long() {
+        let schedule = Schedule::ExponentialBackoff {
+            initial_delay: 100.millis(),
+            max_delay: Option::None,
+            factor: 2,
+            retries: 7,
+            jitter: Jitter::None,
+        };
+
+        assert_that(schedule.validate_within(12700.millis())).is_ok();
+        assert_that(schedule.validate_within(12.secs())).is_equal_to(Err(
+            InvalidSchedule::WorstCaseTotalExceeded { limit: 12.secs() },
+        ));
+        assert_that(
+            Schedule::None {
+                retries: usize::MAX,
+            }
+            .validate_within(StdDuration::ZERO),
+        )
+        .is_ok();
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn schedules_are_serialized_as_tagged_values_with_human_friendly_durations() {
+        let schedule = Schedule::ExponentialBackoff {
+            initial_delay: 250.millis(),
+            max_delay: Some(5.secs()),
+            factor: 2,
+            retries: 8,
+            jitter: Jitter::None,
+        };
+        let json = r#"{"type":"exponential_backoff","initial_delay":"250ms","max_delay":"5s","retries":8}"#;
+
+        assert_that(serde_json::to_string(&schedule).expect("serializable"))
+            .is_equal_to(json.to_owned());
+        assert_that(serde_json::from_str::<Schedule>(json).expect("deserializable"))
+            .is_equal_to(schedule);
+    }
+
+    #[test]
+    fn sequences_yield_the_delays_of_all_segments_in_turn() {
+        static SCHEDULE: Schedule = Schedule::Sequence {
+            segments: Cow::Borrowed(&[
+                Schedule::Fixed {
+                    delay: StdDuration::from_millis(10),
+                    retries: 2,
+                    jitter: Jitter::None,
+                },
+                Schedule::ExponentialBackoff {
+                    initial_delay: StdDuration::from_millis(100),
+                    max_delay: Option::None,
+                    factor: 3,
+                    retries: 2,
+                    jitter: Jitter::Full,
+                },
+            ]),
+        };
+
+        assert_that(SCHEDULE.retries()).is_equal_to(4);
+        assert_that(SCHEDULE.worst_case_total()).is_equal_to(420.millis());
+        assert_that(SCHEDULE.to_string()).is_equal_to(
+            "fixed delay of 10ms, up to 2 retries, then exponential backoff starting at 100ms, tripling on every \
+            retry with full jitter, up to 2 retries"
+                .to_owned(),
+        );
+        assert_that(SCHEDULE.preview().to_string()).is_equal_to(
+            "attempts at t=0, 10ms, 20ms, 20ms..120ms, 20ms..420ms; worst-case total 420ms"
+                .to_owned(),
+        );
+
+        let delays = delays(&SCHEDULE);
+        assert_that(delays.len()).is_equal_to(4);
+        assert_that(delays[..2].to_vec()).contains_exactly([10.millis(), 10.millis()]);
+        assert_that(delays[2]).is_less_or_equal_to(100.millis());
+        assert_that(delays[3]).is_less_or_equal_to(300.millis());
+    }
+
+    #[test]
+    fn sequences_are_validated_as_a_whole_and_per_segment() {
+        let segment = |retries, factor| Schedule::ExponentialBackoff {
+            initial_delay: 1.secs(),
+            max_delay: Option::None,
+            factor,
+            retries,
+            jitter: Jitter::None,
+        };
+        let sequence = |segments: Vec<Schedule>| Schedule::Sequence {
+            segments: Cow::Owned(segments),
+        };
+
+        assert_that(sequence(vec![segment(0, 2), segment(2, 2)]).validate()).is_ok();
+        assert_that(sequence(vec![segment(0, 2), segment(0, 2)]).validate())
+            .is_equal_to(Err(InvalidSchedule::NoRetries));
+        assert_that(sequence(vec![segment(2, 2), segment(0, 1)]).validate())
+            .is_equal_to(Err(InvalidSchedule::NoGrowth { factor: 1 }));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn sequences_and_jitter_are_serialized() {
+        let json = r#"{"type":"sequence","segments":[{"type":"fixed","delay":"1s","retries":2},{"type":"exponential_backoff","initial_delay":"2s","retries":3,"jitter":"equal"}]}"#;
+        let schedule = serde_json::from_str::<Schedule>(json).expect("deserializable");
+
+        assert_that(schedule.retries()).is_equal_to(5);
+        assert_that(serde_json::to_string(&schedule).expect("serializable"))
+            .is_equal_to(json.to_owned());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn exponential_backoffs_serialize_their_factor_unless_it_is_the_default() {
+        let json = r#"{"type":"exponential_backoff","initial_delay":"1s","factor":3,"retries":3}"#;
+        let schedule = serde_json::from_str::<Schedule>(json).expect("deserializable");
+
+        assert_that(schedule.worst_case_total()).is_equal_to(13.secs());
+        assert_that(serde_json::to_string(&schedule).expect("serializable"))
+            .is_equal_to(json.to_owned());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn strategies_can_be_deserialized_directly() {
+        let json = r#"{"type":"fixed","delay":"1.5s","retries":2}"#;
+        let mut strategy = serde_json::from_str::<ScheduleStrategy>(json).expect("deserializable");
+
+        assert_that(strategy.next_delay())
+            .is_some()
+            .is_equal_to(1500.millis());
+        assert_that(strategy.next_delay())
+            .is_some()
+            .is_equal_to(1500.millis());
+        assert_that(strategy.next_delay()).is_none();
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn invalid_durations_are_reported() {
+        let json = r#"{"type":"fixed","delay":"1.5 fortnights","retries":2}"#;
+        let err = serde_json::from_str::<Schedule>(json).expect_err("invalid");
+
+        assert_that(err.to_string()).starts_with("unknown unit \"fortnights\"");
+    }
+}
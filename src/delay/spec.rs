@@ -0,0 +1,659 @@
+use crate::StdDuration;
+use crate::delay::{
+    ExponentialBackoffWithCap, InvalidSchedule, Jitter, Preview, Schedule, ScheduleStrategy,
+};
+use crate::delay_strategy::{BoxedDelayStrategy, DelayStrategy};
+use crate::duration::human::{format_duration, parse_duration};
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A `Schedule` written in a compact textual notation, e.g. for CLI flags or environment variables.
+///
+/// A schedule consists of one or more segments, separated by `then`. Each segment names a strategy and must specify
+/// its number of retries, either through `tries=N` or by appending `*N`:
+///
+/// - `none(tries=3)` or `none()*3`: No delay.
+/// - `fixed(1s, tries=5)` or `fixed(1s)*5`: A static delay.
+/// - `exp(100ms, x2, cap=5s, tries=8)`: An exponentially increasing delay, starting at `100ms`, growing by the
+///   (optional, defaults to `x2`) factor and optionally capped.
+///
+/// Every segment additionally accepts `jitter=none|full|equal` (see `Jitter`).
+/// Durations are written with one of the units `h`, `m`, `s`, `ms`, `us` or `ns`, e.g. `250ms` or `1.5s`.
+///
+/// ```rust
+/// use try_again::{delay::ScheduleSpec, retry};
+///
+/// let spec: ScheduleSpec = "fixed(1ms)*2 then exp(2ms, cap=3ms, jitter=full)*3"
+///     .parse()
+///     .expect("valid schedule");
+///
+/// let outcome = retry(|| -> Result<(), ()> { Ok(()) }).delayed_by(spec.strategy());
+/// assert_eq!(outcome, Ok(()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ScheduleSpec {
+    schedule: Schedule,
+}
+
+impl ScheduleSpec {
+    /// The parsed schedule, e.g. to create a `RetryPolicy` from.
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    /// Checks for configurations which are most likely unintended, see `Schedule::validate`.
+    pub fn validate(&self) -> Result<(), InvalidSchedule> {
+        self.schedule.validate()
+    }
+
+    /// Like `validate`, additionally rejecting schedules whose delays may add up to more than `max_total_delay`.
+    pub fn validate_within(&self, max_total_delay: StdDuration) -> Result<(), InvalidSchedule> {
+        self.schedule.validate_within(max_total_delay)
+    }
+
+    /// The largest possible sum of all delays of this schedule.
+    pub fn worst_case_total(&self) -> StdDuration {
+        self.schedule.worst_case_total()
+    }
+
+    /// Computes all delays of this schedule, see `Preview`.
+    pub fn preview(&self) -> Preview {
+        self.schedule.preview()
+    }
+
+    /// Creates a fresh delay strategy following this schedule.
+    pub fn strategy(&self) -> ScheduleStrategy {
+        self.schedule.strategy()
+    }
+}
+
+impl From<Schedule> for ScheduleSpec {
+    fn from(schedule: Schedule) -> Self {
+        Self { schedule }
+    }
+}
+
+impl From<ScheduleSpec> for Schedule {
+    fn from(spec: ScheduleSpec) -> Self {
+        spec.schedule
+    }
+}
+
+impl Display for ScheduleSpec {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match write_segments(&self.schedule, f, true)? {
+            true => f.write_str("none()*0"),
+            false => Ok(()),
+        }
+    }
+}
+
+/// Writes the notation of every (flattened) segment of `schedule`, returning whether the output is still empty.
+fn write_segments(
+    schedule: &Schedule,
+    f: &mut Formatter<'_>,
+    mut empty: bool,
+) -> Result<bool, std::fmt::Error> {
+    let mut args = Vec::new();
+    let (name, retries, jitter) = match schedule {
+        Schedule::Sequence { segments } => {
+            for segment in segments.iter() {
+                empty = write_segments(segment, f, empty)?;
+            }
+            return Ok(empty);
+        }
+        Schedule::None { retries } => ("none", retries, Jitter::None),
+        Schedule::Fixed {
+            delay,
+            retries,
+            jitter,
+        } => {
+            args.push(format_duration(*delay));
+            ("fixed", retries, *jitter)
+        }
+        Schedule::ExponentialBackoff {
+            initial_delay,
+            max_delay,
+            factor,
+            retries,
+            jitter,
+        } => {
+            args.push(format_duration(*initial_delay));
+            if *factor != ExponentialBackoffWithCap::DEFAULT_FACTOR {
+                args.push(format!("x{factor}"));
+            }
+            if let Some(max_delay) = max_delay {
+                args.push(format!("cap={}", format_duration(*max_delay)));
+            }
+            ("exp", retries, *jitter)
+        }
+    };
+    match jitter {
+        Jitter::None => {}
+        Jitter::Full => args.push("jitter=full".to_owned()),
+        Jitter::Equal => args.push("jitter=equal".to_owned()),
+    }
+    if !empty {
+        f.write_str(" then ")?;
+    }
+    write!(f, "{name}({})*{retries}", args.join(", "))?;
+    Ok(false)
+}
+
+/// Error returned when parsing a `ScheduleSpec` fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseScheduleError {
+    column: usize,
+    message: String,
+}
+
+impl ParseScheduleError {
+    fn new(column: usize, message: impl Into<String>) -> Self {
+        Self {
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// The (1-based) column of the input at which the error was detected.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for ParseScheduleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (at column {})", self.message, self.column)
+    }
+}
+
+impl std::error::Error for ParseScheduleError {}
+
+/// Parses a schedule written in the notation of `ScheduleSpec` directly into a boxed delay strategy.
+///
+/// ```rust
+/// use try_again::{delay, retry};
+///
+/// let strategy = delay::parse_strategy("exp(1ms, cap=2ms)*3").expect("valid schedule");
+///
+/// let outcome = retry(|| -> Result<(), ()> { Err(()) }).delayed_by(strategy);
+/// assert_eq!(outcome, Err(()));
+/// ```
+pub fn parse_strategy(input: &str) -> Result<BoxedDelayStrategy, ParseScheduleError> {
+    input
+        .parse::<ScheduleSpec>()
+        .map(|spec| spec.strategy().boxed())
+}
+
+impl FromStr for ScheduleSpec {
+    type Err = ParseScheduleError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Parser {
+            tokens: tokenize(input)?,
+            position: 0,
+        }
+        .parse_schedule()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenKind<'a> {
+    Ident(&'a str),
+    Literal(&'a str),
+    OpenParen,
+    CloseParen,
+    Comma,
+    Equals,
+    Star,
+    End,
+}
+
+impl Display for TokenKind<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenKind::Ident(text) | TokenKind::Literal(text) => write!(f, "\"{text}\""),
+            TokenKind::OpenParen => f.write_str("\"(\""),
+            TokenKind::CloseParen => f.write_str("\")\""),
+            TokenKind::Comma => f.write_str("\",\""),
+            TokenKind::Equals => f.write_str("\"=\""),
+            TokenKind::Star => f.write_str("\"*\""),
+            TokenKind::End => f.write_str("end of input"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token<'a> {
+    kind: TokenKind<'a>,
+    column: usize,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token<'_>>, ParseScheduleError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().enumerate().peekable();
+    while let Some((index, (start, c))) = chars.next() {
+        let column = index + 1;
+        let mut consume_while = |accept: fn(char) -> bool| {
+            let mut end = start + c.len_utf8();
+            while let Some((_, (offset, next))) = chars.peek().copied() {
+                if !accept(next) {
+                    break;
+                }
+                end = offset + next.len_utf8();
+                chars.next();
+            }
+            &input[start..end]
+        };
+        let kind = match c {
+            c if c.is_whitespace() => continue,
+            '(' => TokenKind::OpenParen,
+            ')' => TokenKind::CloseParen,
+            ',' => TokenKind::Comma,
+            '=' => TokenKind::Equals,
+            '*' => TokenKind::Star,
+            c if c.is_ascii_digit() || c == '.' => {
+                TokenKind::Literal(consume_while(|c| c.is_alphanumeric() || c == '.'))
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                TokenKind::Ident(consume_while(|c| c.is_alphanumeric() || c == '_'))
+            }
+            c => {
+                return Err(ParseScheduleError::new(
+                    column,
+                    format!("unexpected character \"{c}\""),
+                ));
+            }
+        };
+        tokens.push(Token { kind, column });
+    }
+    tokens.push(Token {
+        kind: TokenKind::End,
+        column: input.chars().count() + 1,
+    });
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token<'a>>,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Token<'a> {
+        self.tokens[self.position.min(self.tokens.len() - 1)]
+    }
+
+    fn advance(&mut self) -> Token<'a> {
+        let token = self.peek();
+        self.position += 1;
+        token
+    }
+
+    fn expect(&mut self, kind: TokenKind<'static>) -> Result<Token<'a>, ParseScheduleError> {
+        let token = self.advance();
+        match token.kind == kind {
+            true => Ok(token),
+            false => Err(unexpected(token, &kind.to_string())),
+        }
+    }
+
+    fn parse_schedule(mut self) -> Result<ScheduleSpec, ParseScheduleError> {
+        let mut segments = vec![self.parse_segment()?];
+        loop {
+            let token = self.advance();
+            match token.kind {
+                TokenKind::End => {
+                    let schedule = match segments.len() {
+                        1 => segments.pop().expect("one segment"),
+                        _ => Schedule::Sequence {
+                            segments: Cow::Owned(segments),
+                        },
+                    };
+                    return Ok(ScheduleSpec { schedule });
+                }
+                TokenKind::Ident("then") => segments.push(self.parse_segment()?),
+                _ => return Err(unexpected(token, "\"then\" or end of input")),
+            }
+        }
+    }
+
+    fn parse_segment(&mut self) -> Result<Schedule, ParseScheduleError> {
+        let name = self.advance();
+        let name_text = match name.kind {
+            TokenKind::Ident(text @ ("none" | "fixed" | "exp")) => text,
+            TokenKind::Ident(text) => {
+                return Err(ParseScheduleError::new(
+                    name.column,
+                    format!("unknown strategy \"{text}\", expected one of none, fixed, exp"),
+                ));
+            }
+            _ => return Err(unexpected(name, "a strategy (none, fixed or exp)")),
+        };
+        self.expect(TokenKind::OpenParen)?;
+
+        let mut delay = Option::None;
+        let mut factor = Option::None;
+        let mut max_delay = Option::None;
+        let mut tries = Option::None;
+        let mut jitter = Option::None;
+
+        let mut first = true;
+        loop {
+            let mut token = self.advance();
+            if token.kind == TokenKind::CloseParen {
+                break;
+            }
+            if !first {
+                if token.kind != TokenKind::Comma {
+                    return Err(unexpected(token, "\",\" or \")\""));
+                }
+                token = self.advance();
+            }
+            first = false;
+
+            match token.kind {
+                TokenKind::Literal(text) if name_text != "none" => {
+                    set_once(&mut delay, token, "delay", parse_duration_at(token, text)?)?;
+                }
+                TokenKind::Ident(text)
+                    if name_text == "exp"
+                        && text.len() > 1
+                        && text.starts_with('x')
+                        && self.peek().kind != TokenKind::Equals =>
+                {
+                    let value = text[1..].parse::<u32>().map_err(|_| {
+                        ParseScheduleError::new(
+                            token.column,
+                            format!("invalid growth factor \"{text}\", expected e.g. x2"),
+                        )
+                    })?;
+                    set_once(&mut factor, token, "growth factor", value)?;
+                }
+                TokenKind::Ident(key) => {
+                    self.expect(TokenKind::Equals)?;
+                    let value = self.advance();
+                    let (TokenKind::Literal(text) | TokenKind::Ident(text)) = value.kind else {
+                        return Err(unexpected(value, &format!("a value for \"{key}\"")));
+                    };
+                    match key {
+                        "tries" => set_once(&mut tries, token, key, parse_count_at(value, text)?)?,
+                        "cap" if name_text == "exp" => {
+                            set_once(&mut max_delay, token, key, parse_duration_at(value, text)?)?
+                        }
+                        "jitter" => {
+                            set_once(&mut jitter, token, key, parse_jitter_at(value, text)?)?
+                        }
+                        _ => {
+                            return Err(ParseScheduleError::new(
+                                token.column,
+                                format!("unknown parameter \"{key}\" for {name_text}(..)"),
+                            ));
+                        }
+                    }
+                }
+                _ => return Err(unexpected(token, "a parameter or \")\"")),
+            }
+        }
+
+        if self.peek().kind == TokenKind::Star {
+            let star = self.advance();
+            let count = self.advance();
+            let TokenKind::Literal(text) = count.kind else {
+                return Err(unexpected(count, "the number of retries"));
+            };
+            set_once(&mut tries, star, "tries", parse_count_at(count, text)?)?;
+        }
+
+        let delay = match (name_text, delay) {
+            ("none", _) => StdDuration::ZERO,
+            (_, Some(delay)) => delay,
+            (_, Option::None) => {
+                return Err(ParseScheduleError::new(
+                    name.column,
+                    format!("{name_text}(..) requires a delay, e.g. {name_text}(1s)"),
+                ));
+            }
+        };
+        let tries = tries.ok_or_else(|| {
+            ParseScheduleError::new(
+                name.column,
+                format!(
+                    "{name_text}(..) never ends, specify the number of retries with tries=N or *N"
+                ),
+            )
+        })?;
+        let jitter = jitter.unwrap_or_default();
+
+        Ok(match name_text {
+            "none" => Schedule::None { retries: tries },
+            "fixed" => Schedule::Fixed {
+                delay,
+                retries: tries,
+                jitter,
+            },
+            _ => Schedule::ExponentialBackoff {
+                initial_delay: delay,
+                max_delay,
+                factor: factor.unwrap_or(ExponentialBackoffWithCap::DEFAULT_FACTOR),
+                retries: tries,
+                jitter,
+            },
+        })
+    }
+}
+
+fn unexpected(token: Token<'_>, expected: &str) -> ParseScheduleError {
+    ParseScheduleError::new(
+        token.column,
+        format!("expected {expected}, found {}", token.kind),
+    )
+}
+
+fn set_once<T>(
+    slot: &mut Option<T>,
+    token: Token<'_>,
+    name: &str,
+    value: T,
+) -> Result<(), ParseScheduleError> {
+    match slot.replace(value) {
+        Some(_) => Err(ParseScheduleError::new(
+            token.column,
+            format!("{name} specified more than once"),
+        )),
+        Option::None => Ok(()),
+    }
+}
+
+fn parse_duration_at(token: Token<'_>, text: &str) -> Result<StdDuration, ParseScheduleError> {
+    parse_duration(text).map_err(|err| ParseScheduleError::new(token.column, err.to_string()))
+}
+
+fn parse_count_at(token: Token<'_>, text: &str) -> Result<usize, ParseScheduleError> {
+    text.parse().map_err(|_| {
+        ParseScheduleError::new(
+            token.column,
+            format!("invalid number of retries \"{text}\""),
+        )
+    })
+}
+
+fn parse_jitter_at(token: Token<'_>, text: &str) -> Result<Jitter, ParseScheduleError> {
+    match text {
+        "none" => Ok(Jitter::None),
+        "full" => Ok(Jitter::Full),
+        "equal" => Ok(Jitter::Equal),
+        _ => Err(ParseScheduleError::new(
+            token.column,
+            format!("unknown jitter \"{text}\", expected one of none, full, equal"),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::IntoStdDuration;
+    use assertr::prelude::*;
+
+    fn delays(spec: &str) -> Vec<StdDuration> {
+        let mut strategy = spec
+            .parse::<ScheduleSpec>()
+            .expect("valid schedule")
+            .strategy();
+        std::iter::from_fn(|| strategy.next_delay()).collect()
+    }
+
+    fn error(spec: &str) -> ParseScheduleError {
+        spec.parse::<ScheduleSpec>().expect_err("invalid schedule")
+    }
+
+    #[test]
+    fn parses_exponential_backoff_with_all_parameters() {
+        assert_that(delays("exp(100ms, x3, cap=1s, tries=4)")).contains_exactly([
+            100.millis(),
+            300.millis(),
+            900.millis(),
+            1.secs(),
+        ]);
+    }
+
+    #[test]
+    fn parses_sequences_of_segments() {
+        assert_that(delays("fixed(1s)*2 then exp(2s)*3 then none(tries=1)")).contains_exactly([
+            1.secs(),
+            1.secs(),
+            2.secs(),
+            4.secs(),
+            8.secs(),
+            StdDuration::ZERO,
+        ]);
+    }
+
+    #[test]
+    fn applies_jitter() {
+        let delays = delays("fixed(100ms, jitter=equal)*20");
+
+        assert_that(delays.len()).is_equal_to(20);
+        for delay in delays {
+            assert_that(delay).is_greater_or_equal_to(50.millis());
+            assert_that(delay).is_less_or_equal_to(100.millis());
+        }
+    }
+
+    #[test]
+    fn display_produces_a_parsable_representation() {
+        let spec = "exp(100ms, x2, cap=5s, tries=8, jitter=full) then fixed(1.5s)*2"
+            .parse::<ScheduleSpec>()
+            .expect("valid schedule");
+
+        assert_that(spec.to_string())
+            .is_equal_to("exp(100ms, cap=5s, jitter=full)*8 then fixed(1500ms)*2".to_owned());
+        assert_that(spec.to_string().parse::<ScheduleSpec>())
+            .is_ok()
+            .is_equal_to(spec);
+    }
+
+    #[test]
+    fn errors_point_at_the_offending_column() {
+        let err = error("fixed(1s)*2 then exp(2s, cap=5parsecs)*3");
+        assert_that(err.column()).is_equal_to(30);
+        assert_that(err.message()).starts_with("unknown unit \"parsecs\"");
+
+        let err = error("fixed(1s) then none()*1");
+        assert_that(err.column()).is_equal_to(1);
+        assert_that(err.message()).contains("never ends");
+
+        let err = error("exp(1s, x2, tires=3)");
+        assert_that(err.column()).is_equal_to(13);
+        assert_that(err.to_string())
+            .is_equal_to("unknown parameter \"tires\" for exp(..) (at column 13)".to_owned());
+
+        let err = error("fixed(1s)*2 or none()*1");
+        assert_that(err.column()).is_equal_to(13);
+        assert_that(err.message()).is_equal_to("expected \"then\" or end of input, found \"or\"");
+
+        let err = error("fixed(1s, tries=2)*3");
+        assert_that(err.column()).is_equal_to(19);
+        assert_that(err.message()).is_equal_to("tries specified more than once");
+
+        let err = error("fixed(tries=2)");
+        assert_that(err.message()).is_equal_to("fixed(..) requires a delay, e.g. fixed(1s)");
+    }
+
+    #[test]
+    fn validation_checks_every_segment_and_the_total() {
+        let spec = |spec: &str| spec.parse::<ScheduleSpec>().expect("valid schedule");
+
+        assert_that(spec("fixed(1s)*2 then exp(5s, cap=1s)*3").validate()).is_equal_to(Err(
+            InvalidSchedule::CapBelowInitialDelay {
+                initial_delay: 5.secs(),
+                max_delay: 1.secs(),
+            },
+        ));
+        assert_that(spec("exp(1s, x1)*3").validate())
+            .is_equal_to(Err(InvalidSchedule::NoGrowth { factor: 1 }));
+        assert_that(spec("fixed(1s)*0").validate()).is_equal_to(Err(InvalidSchedule::NoRetries));
+
+        let spec = spec("fixed(1s)*2 then exp(1s, jitter=full)*2");
+        assert_that(spec.worst_case_total()).is_equal_to(5.secs());
+        assert_that(spec.validate_within(5.secs())).is_ok();
+        assert_that(spec.validate_within(4.secs())).is_equal_to(Err(
+            InvalidSchedule::WorstCaseTotalExceeded { limit: 4.secs() },
+        ));
+    }
+
+    #[test]
+    fn parses_directly_into_a_boxed_strategy() {
+        let mut strategy = parse_strategy("fixed(1s)*2 then none()*1").expect("valid schedule");
+
+        assert_that(strategy.next_delay()).is_equal_to(Some(1.secs()));
+        assert_that(strategy.next_delay()).is_equal_to(Some(1.secs()));
+        assert_that(strategy.next_delay()).is_equal_to(Some(StdDuration::ZERO));
+        assert_that(strategy.next_delay()).is_none();
+        assert_that(parse_strategy("fixed(1s)").is_err()).is_true();
+    }
+
+    #[test]
+    fn parses_into_a_schedule() {
+        let spec: ScheduleSpec = "fixed(1s)*2 then exp(2s, x3, jitter=full)*3"
+            .parse()
+            .expect("valid schedule");
+
+        assert_that(Schedule::from(spec)).is_equal_to(Schedule::Sequence {
+            segments: Cow::Owned(vec![
+                Schedule::Fixed {
+                    delay: 1.secs(),
+                    retries: 2,
+                    jitter: Jitter::None,
+                },
+                Schedule::ExponentialBackoff {
+                    initial_delay: 2.secs(),
+                    max_delay: Option::None,
+                    factor: 3,
+                    retries: 3,
+                    jitter: Jitter::Full,
+                },
+            ]),
+        });
+
+        let spec: ScheduleSpec = "none(tries=2)".parse().expect("valid schedule");
+        assert_that(spec.schedule().clone()).is_equal_to(Schedule::None { retries: 2 });
+    }
+
+    #[test]
+    fn previews_span_all_segments() {
+        let spec: ScheduleSpec = "fixed(100ms)*2 then exp(1s, jitter=full)*1"
+            .parse()
+            .expect("valid schedule");
+
+        assert_that(spec.preview().to_string()).is_equal_to(
+            "attempts at t=0, 100ms, 200ms, 200ms..1200ms; worst-case total 1200ms".to_owned(),
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use crate::StdDuration;
+use crate::duration::human::format_duration;
+use std::fmt::{Display, Formatter};
+
+/// A delay strategy or schedule configuration which is accepted by the constructors, but most likely not what was
+/// intended. Returned by the `validate` methods and the `try_` constructors.
+///
+/// Only `Schedule` and `ScheduleSpec` are checked for `NoRetries`; the `take` of the raw strategies is not validated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InvalidSchedule {
+    /// The schedule never retries.
+    NoRetries,
+
+    /// An exponential backoff starts at zero, so every delay stays zero.
+    ZeroInitialDelay,
+
+    /// An exponential backoff uses a factor which never increases the delay.
+    NoGrowth { factor: u32 },
+
+    /// An exponential backoff is capped below its initial delay, yielding the initial delay once and the (smaller)
+    /// cap afterward.
+    CapBelowInitialDelay {
+        initial_delay: StdDuration,
+        max_delay: StdDuration,
+    },
+
+    /// The sum of all delays may exceed the configured limit.
+    WorstCaseTotalExceeded { limit: StdDuration },
+}
+
+impl Display for InvalidSchedule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidSchedule::NoRetries => write!(f, "schedule never retries"),
+            InvalidSchedule::ZeroInitialDelay => {
+                write!(f, "initial delay of zero keeps every delay at zero")
+            }
+            InvalidSchedule::NoGrowth { factor } => {
+                write!(f, "factor of {factor} never increases the delay")
+            }
+            InvalidSchedule::CapBelowInitialDelay {
+                initial_delay,
+                max_delay,
+            } => write!(
+                f,
+                "cap of {} is below the initial delay of {}",
+                format_duration(*max_delay),
+                format_duration(*initial_delay)
+            ),
+            InvalidSchedule::WorstCaseTotalExceeded { limit } => write!(
+                f,
+                "worst-case total delay exceeds the limit of {}",
+                format_duration(*limit)
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InvalidSchedule {}
+
+pub(crate) fn validate_exponential_backoff(
+    initial_delay: StdDuration,
+    max_delay: Option<StdDuration>,
+    factor: u32,
+) -> Result<(), InvalidSchedule> {
+    if initial_delay.is_zero() {
+        return Err(InvalidSchedule::ZeroInitialDelay);
+    }
+    if factor < 2 {
+        return Err(InvalidSchedule::NoGrowth { factor });
+    }
+    match max_delay {
+        Some(max_delay) if max_delay < initial_delay => {
+            Err(InvalidSchedule::CapBelowInitialDelay {
+                initial_delay,
+                max_delay,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+pub(crate) fn validate_worst_case_total(
+    total: StdDuration,
+    limit: StdDuration,
+) -> Result<(), InvalidSchedule> {
+    match total > limit {
+        true => Err(InvalidSchedule::WorstCaseTotalExceeded { limit }),
+        false => Ok(()),
+    }
+}
+
+/// Sums up the delays of `retries` retries, starting at `delay` and growing by `factor` up to `max_delay`.
+///
+/// Once the delay stops changing, the remaining retries are accounted for at once, so arbitrarily large retry counts
+/// are handled in constant time.
+pub(crate) fn total_delay(
+    mut delay: StdDuration,
+    max_delay: Option<StdDuration>,
+    factor: u32,
+    retries: usize,
+) -> StdDuration {
+    let mut total = StdDuration::ZERO;
+    for done in 0..retries {
+        let next = delay
+            .checked_mul(factor)
+            .unwrap_or(StdDuration::MAX)
+            .min(max_delay.unwrap_or(StdDuration::MAX));
+        if next == delay {
+            let remaining = (retries - done) as u128;
+            let nanos = delay.as_nanos().saturating_mul(remaining);
+            return total.saturating_add(from_nanos_saturating(nanos));
+        }
+        total = total.saturating_add(delay);
+        delay = next;
+    }
+    total
+}
+
+fn from_nanos_saturating(nanos: u128) -> StdDuration {
+    const NANOS_PER_SEC: u128 = 1_000_000_000;
+    match u64::try_from(nanos / NANOS_PER_SEC) {
+        Ok(secs) => StdDuration::new(secs, (nanos % NANOS_PER_SEC) as u32),
+        Err(_) => StdDuration::MAX,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::IntoStdDuration;
+    use assertr::prelude::*;
+
+    #[test]
+    fn total_delay_sums_up_growing_and_capped_delays() {
+        assert_that(total_delay(100.millis(), None, 2, 4)).is_equal_to(1500.millis());
+        assert_that(total_delay(100.millis(), Some(300.millis()), 2, 4)).is_equal_to(900.millis());
+        assert_that(total_delay(1.secs(), Some(1.secs()), 1, 3)).is_equal_to(3.secs());
+    }
+
+    #[test]
+    fn total_delay_handles_huge_retry_counts() {
+        assert_that(total_delay(1.secs(), None, 2, usize::MAX)).is_equal_to(StdDuration::MAX);
+        assert_that(total_delay(StdDuration::ZERO, None, 2, usize::MAX))
+            .is_equal_to(StdDuration::ZERO);
+    }
+}
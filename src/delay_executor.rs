@@ -1,14 +1,192 @@
 use crate::StdDuration;
+use crate::clock::Clock;
+use crate::duration::ToStdDuration;
 use std::fmt::Debug;
+#[cfg(feature = "async")]
+use std::fmt::Formatter;
+#[cfg(feature = "async")]
+use std::future::Future;
+#[cfg(feature = "async")]
+use std::pin::Pin;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::Instant;
 
+/// Puts the retry loop to sleep between attempts.
+///
+/// Implementations sleeping for `StdDuration`s should apply the time scale of `overrides` through
+/// `overrides::scaled`, like the provided executors do.
 pub trait DelayExecutor<Delay>: Debug {
     fn delay_by(&self, by: Delay);
+
+    /// Sleeps until `by` has passed since `since`, returning immediately when that deadline already passed.
+    ///
+    /// `since` was read from `clock`, the clock of the retry options, which must be used to measure the time passed.
+    ///
+    /// Used instead of `delay_by` when delays are anchored at the start of the attempt (see `DelayAnchor`). Defaults
+    /// to sleeping for the full delay, so executors without a notion of deadlines keep working, but drift.
+    fn delay_since(&self, by: Delay, since: Instant, clock: &dyn Clock) {
+        let _ = (since, clock);
+        self.delay_by(by)
+    }
+
+    /// Whether this executor was cancelled, e.g. through the `CancelHandle` of a `CancellableThreadSleep`.
+    ///
+    /// A cancelled executor stops the retry loop instead of starting another attempt.
+    fn is_cancelled(&self) -> bool {
+        false
+    }
+}
+
+/// A type-erased executor, e.g. chosen at runtime from configuration and stored in a struct.
+pub type BoxedDelayExecutor<Delay = StdDuration> = Box<dyn DelayExecutor<Delay> + Send + Sync>;
+
+impl<Delay, Exec: DelayExecutor<Delay> + ?Sized> DelayExecutor<Delay> for Box<Exec> {
+    fn delay_by(&self, by: Delay) {
+        (**self).delay_by(by)
+    }
+
+    fn delay_since(&self, by: Delay, since: Instant, clock: &dyn Clock) {
+        (**self).delay_since(by, since, clock)
+    }
+
+    fn is_cancelled(&self) -> bool {
+        (**self).is_cancelled()
+    }
 }
 
 #[cfg(feature = "async")]
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot sleep between retries of an async operation",
+    note = "without a runtime feature, `DefaultAsyncSleep` cannot sleep; choose an executor through `with_executor`"
+)]
 pub trait AsyncDelayExecutor<Delay>: Debug {
     #[allow(async_fn_in_trait)]
     async fn delay_by(&self, by: Delay);
+
+    /// Sleeps until `by` has passed since `since`, see `DelayExecutor::delay_since`.
+    #[allow(async_fn_in_trait)]
+    async fn delay_since(&self, by: Delay, since: Instant, clock: &dyn Clock) {
+        let _ = (since, clock);
+        self.delay_by(by).await
+    }
+}
+
+/// Variant of `AsyncDelayExecutor` guaranteeing `Send` futures, required by `retry_async_send_with_options`.
+///
+/// The future of `AsyncDelayExecutor::delay_by` cannot be proven to be `Send` in generic code, preventing the retry
+/// loop from being spawned onto a multi-threaded runtime.
+#[cfg(feature = "async")]
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot sleep between retries of a `Send` async operation",
+    note = "without a runtime feature, `DefaultAsyncSleep` cannot sleep; choose an executor through `with_executor`"
+)]
+pub trait SendAsyncDelayExecutor<Delay>: Debug + Send + Sync {
+    fn delay_by(&self, by: Delay) -> impl Future<Output = ()> + Send;
+
+    /// Sleeps until `by` has passed since `since`, see `DelayExecutor::delay_since`.
+    fn delay_since(
+        &self,
+        by: Delay,
+        since: Instant,
+        clock: &dyn Clock,
+    ) -> impl Future<Output = ()> + Send {
+        let _ = (since, clock);
+        self.delay_by(by)
+    }
+}
+
+/// A type-erased `AsyncDelayExecutor`.
+///
+/// `AsyncDelayExecutor` uses `async fn`, so it cannot be used as a trait object. This wrapper boxes the futures of
+/// any executor instead, so executors can be chosen at runtime and stored in a struct without being generic over them.
+///
+/// ```rust
+/// # #[cfg(feature = "async-tokio")] {
+/// use try_again::delay_executor::{DynAsyncDelayExecutor, TokioSleep};
+///
+/// struct Client {
+///     delay_executor: DynAsyncDelayExecutor,
+/// }
+///
+/// let client = Client {
+///     delay_executor: DynAsyncDelayExecutor::new(TokioSleep),
+/// };
+/// # }
+/// ```
+#[cfg(feature = "async")]
+pub struct DynAsyncDelayExecutor<Delay = StdDuration> {
+    inner: Box<dyn ErasedAsyncDelayExecutor<Delay> + Send + Sync>,
+}
+
+#[cfg(feature = "async")]
+impl<Delay> DynAsyncDelayExecutor<Delay> {
+    pub fn new<Exec>(delay_executor: Exec) -> Self
+    where
+        Exec: AsyncDelayExecutor<Delay> + Send + Sync + 'static,
+    {
+        Self {
+            inner: Box::new(delay_executor),
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<Delay> Debug for DynAsyncDelayExecutor<Delay> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("DynAsyncDelayExecutor")
+            .field(&self.inner)
+            .finish()
+    }
+}
+
+#[cfg(feature = "async")]
+impl<Delay> AsyncDelayExecutor<Delay> for DynAsyncDelayExecutor<Delay> {
+    async fn delay_by(&self, by: Delay) {
+        self.inner.delay_by_boxed(by).await
+    }
+
+    async fn delay_since(&self, by: Delay, since: Instant, clock: &dyn Clock) {
+        self.inner.delay_since_boxed(by, since, clock).await
+    }
+}
+
+/// Dyn-compatible counterpart of `AsyncDelayExecutor`, implemented for every `AsyncDelayExecutor`.
+#[cfg(feature = "async")]
+trait ErasedAsyncDelayExecutor<Delay>: Debug {
+    fn delay_by_boxed<'a>(&'a self, by: Delay) -> Pin<Box<dyn Future<Output = ()> + 'a>>
+    where
+        Delay: 'a;
+
+    fn delay_since_boxed<'a>(
+        &'a self,
+        by: Delay,
+        since: Instant,
+        clock: &'a dyn Clock,
+    ) -> Pin<Box<dyn Future<Output = ()> + 'a>>
+    where
+        Delay: 'a;
+}
+
+#[cfg(feature = "async")]
+impl<Delay, Exec: AsyncDelayExecutor<Delay>> ErasedAsyncDelayExecutor<Delay> for Exec {
+    fn delay_by_boxed<'a>(&'a self, by: Delay) -> Pin<Box<dyn Future<Output = ()> + 'a>>
+    where
+        Delay: 'a,
+    {
+        Box::pin(self.delay_by(by))
+    }
+
+    fn delay_since_boxed<'a>(
+        &'a self,
+        by: Delay,
+        since: Instant,
+        clock: &'a dyn Clock,
+    ) -> Pin<Box<dyn Future<Output = ()> + 'a>>
+    where
+        Delay: 'a,
+    {
+        Box::pin(self.delay_since(by, since, clock))
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -16,10 +194,200 @@ pub struct ThreadSleep;
 
 impl<Delay: Into<StdDuration>> DelayExecutor<Delay> for ThreadSleep {
     fn delay_by(&self, delay: Delay) {
-        std::thread::sleep(delay.into())
+        std::thread::sleep(scaled(delay))
+    }
+
+    fn delay_since(&self, delay: Delay, since: Instant, clock: &dyn Clock) {
+        std::thread::sleep(remaining(scaled(delay), since, clock))
+    }
+}
+
+/// Puts the current thread to sleep like `ThreadSleep`, but can be woken up early through its `CancelHandle`, e.g. to
+/// let a worker thread shut down without waiting out a long backoff.
+///
+/// Once cancelled, the executor no longer sleeps and the retry loop stops (see `retry_with_cancellation`).
+///
+/// ```rust
+/// use try_again::delay_executor::CancellableThreadSleep;
+/// use try_again::{Cancelled, IntoStdDuration, RetryOptions, delay, retry_with_cancellation};
+///
+/// let sleep = CancellableThreadSleep::new();
+/// let cancel = sleep.cancel_handle();
+///
+/// let worker = std::thread::spawn(move || {
+///     retry_with_cancellation(
+///         || -> Result<(), ()> { Err(()) },
+///         RetryOptions::builder()
+///             .strategy(delay::Fixed::of(30.secs()).take(5))
+///             .executor(sleep)
+///             .build(),
+///     )
+/// });
+///
+/// cancel.cancel();
+/// let outcome = worker.join().expect("worker not to panic");
+/// assert!(matches!(outcome, Err(Cancelled { .. })));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancellableThreadSleep {
+    handle: CancelHandle,
+}
+
+impl CancellableThreadSleep {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an executor cancelled through an existing `handle`, e.g. the one of `signals::install` (feature
+    /// `signals`). All executors sharing a handle are cancelled together.
+    pub fn with_cancel_handle(handle: CancelHandle) -> Self {
+        Self { handle }
+    }
+
+    /// A handle cancelling this executor, which can be sent to other threads.
+    pub fn cancel_handle(&self) -> CancelHandle {
+        self.handle.clone()
+    }
+}
+
+impl<Delay: Into<StdDuration>> DelayExecutor<Delay> for CancellableThreadSleep {
+    fn delay_by(&self, delay: Delay) {
+        self.handle.wait_timeout(scaled(delay));
+    }
+
+    fn delay_since(&self, delay: Delay, since: Instant, clock: &dyn Clock) {
+        self.handle
+            .wait_timeout(remaining(scaled(delay), since, clock));
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.handle.is_cancelled()
+    }
+}
+
+/// Cancels a `CancellableThreadSleep`, waking up a thread sleeping through it. Clones cancel the same executor.
+///
+/// With the `async` feature, `cancelled` turns the handle into a signal for `CancelSignal`, so a single handle can stop
+/// both synchronous and asynchronous retry loops.
+#[derive(Debug, Clone, Default)]
+pub struct CancelHandle {
+    state: Arc<(Mutex<CancelState>, Condvar)>,
+}
+
+#[derive(Debug, Default)]
+struct CancelState {
+    cancelled: bool,
+    /// The wakers of all pending `cancelled` futures, each removing its own slot when dropped.
+    #[cfg(feature = "async")]
+    wakers: std::collections::HashMap<u64, std::task::Waker>,
+    #[cfg(feature = "async")]
+    next_waker_slot: u64,
+}
+
+impl CancelHandle {
+    /// Cancels the executor. Cancellation cannot be undone.
+    pub fn cancel(&self) {
+        let (state, wakeup) = &*self.state;
+        let mut state = lock(state);
+        state.cancelled = true;
+        #[cfg(feature = "async")]
+        state.wakers.drain().for_each(|(_, waker)| waker.wake());
+        wakeup.notify_all();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        lock(&self.state.0).cancelled
+    }
+
+    /// Completes once this handle is cancelled, e.g. to be used as `CancelSignal::on(handle.cancelled())`.
+    ///
+    /// Does not depend on any particular runtime.
+    #[cfg(feature = "async")]
+    pub fn cancelled(&self) -> impl Future<Output = ()> + Send + use<> {
+        WaitForCancel {
+            handle: self.clone(),
+            slot: None,
+        }
+    }
+
+    /// Blocks the current thread for `timeout`, or until cancelled.
+    fn wait_timeout(&self, timeout: StdDuration) {
+        let (state, wakeup) = &*self.state;
+        let _ = wakeup
+            .wait_timeout_while(lock(state), timeout, |state| !state.cancelled)
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
     }
 }
 
+/// Applies the time scale of `overrides` to `delay`, which all provided executors sleep for.
+fn scaled(delay: impl Into<StdDuration>) -> StdDuration {
+    crate::overrides::scaled(delay.into())
+}
+
+/// The part of `delay` still left at the current time of `clock`, when it started at `since`.
+///
+/// A deadline beyond what `Instant` can represent is never reached, so the full delay is left in that case.
+pub(crate) fn remaining(
+    delay: impl Into<StdDuration>,
+    since: Instant,
+    clock: &dyn Clock,
+) -> StdDuration {
+    let delay = delay.into();
+    match since.checked_add(delay) {
+        Some(deadline) => deadline.saturating_duration_since(clock.now()),
+        None => delay,
+    }
+}
+
+/// The future of `CancelHandle::cancelled`, registering its waker in a slot of its own until it completes or is dropped.
+#[cfg(feature = "async")]
+struct WaitForCancel {
+    handle: CancelHandle,
+    slot: Option<u64>,
+}
+
+#[cfg(feature = "async")]
+impl Future for WaitForCancel {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> std::task::Poll<()> {
+        let this = &mut *self;
+        let mut state = lock(&this.handle.state.0);
+        if state.cancelled {
+            this.slot = None;
+            return std::task::Poll::Ready(());
+        }
+        let slot = *this.slot.get_or_insert_with(|| {
+            let slot = state.next_waker_slot;
+            state.next_waker_slot += 1;
+            slot
+        });
+        match state.wakers.get_mut(&slot) {
+            Some(waker) => waker.clone_from(cx.waker()),
+            None => {
+                state.wakers.insert(slot, cx.waker().clone());
+            }
+        }
+        std::task::Poll::Pending
+    }
+}
+
+#[cfg(feature = "async")]
+impl Drop for WaitForCancel {
+    fn drop(&mut self) {
+        if let Some(slot) = self.slot {
+            lock(&self.handle.state.0).wakers.remove(&slot);
+        }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    // The state is never left inconsistent, so a poisoned lock can safely be recovered.
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
 #[derive(Debug, Clone, Copy)]
 #[cfg(feature = "async-tokio")]
 pub struct TokioSleep;
@@ -27,6 +395,259 @@ pub struct TokioSleep;
 #[cfg(feature = "async-tokio")]
 impl<Delay: Into<StdDuration>> AsyncDelayExecutor<Delay> for TokioSleep {
     async fn delay_by(&self, delay: Delay) {
-        tokio::time::sleep(delay.into()).await
+        tokio::time::sleep(scaled(delay)).await
+    }
+
+    async fn delay_since(&self, delay: Delay, since: Instant, clock: &dyn Clock) {
+        tokio::time::sleep(remaining(scaled(delay), since, clock)).await
+    }
+}
+
+#[cfg(feature = "async-tokio")]
+impl<Delay: Into<StdDuration>> SendAsyncDelayExecutor<Delay> for TokioSleep {
+    fn delay_by(&self, delay: Delay) -> impl Future<Output = ()> + Send {
+        tokio::time::sleep(scaled(delay))
+    }
+
+    fn delay_since(
+        &self,
+        delay: Delay,
+        since: Instant,
+        clock: &dyn Clock,
+    ) -> impl Future<Output = ()> + Send {
+        tokio::time::sleep(remaining(scaled(delay), since, clock))
+    }
+}
+
+/// Sleeps through the `async-std` runtime.
+#[derive(Debug, Clone, Copy)]
+#[cfg(feature = "async-std")]
+pub struct AsyncStdSleep;
+
+#[cfg(feature = "async-std")]
+impl<Delay: Into<StdDuration>> AsyncDelayExecutor<Delay> for AsyncStdSleep {
+    async fn delay_by(&self, delay: Delay) {
+        async_std::task::sleep(scaled(delay)).await
+    }
+
+    async fn delay_since(&self, delay: Delay, since: Instant, clock: &dyn Clock) {
+        async_std::task::sleep(remaining(scaled(delay), since, clock)).await
+    }
+}
+
+#[cfg(feature = "async-std")]
+impl<Delay: Into<StdDuration>> SendAsyncDelayExecutor<Delay> for AsyncStdSleep {
+    fn delay_by(&self, delay: Delay) -> impl Future<Output = ()> + Send {
+        async_std::task::sleep(scaled(delay))
+    }
+
+    fn delay_since(
+        &self,
+        delay: Delay,
+        since: Instant,
+        clock: &dyn Clock,
+    ) -> impl Future<Output = ()> + Send {
+        async_std::task::sleep(remaining(scaled(delay), since, clock))
+    }
+}
+
+/// Sleeps through the timers of `async-io`, the reactor used by `smol`.
+#[derive(Debug, Clone, Copy)]
+#[cfg(feature = "async-smol")]
+pub struct SmolSleep;
+
+#[cfg(feature = "async-smol")]
+impl<Delay: Into<StdDuration>> AsyncDelayExecutor<Delay> for SmolSleep {
+    async fn delay_by(&self, delay: Delay) {
+        async_io::Timer::after(scaled(delay)).await;
+    }
+
+    async fn delay_since(&self, delay: Delay, since: Instant, clock: &dyn Clock) {
+        async_io::Timer::after(remaining(scaled(delay), since, clock)).await;
+    }
+}
+
+#[cfg(feature = "async-smol")]
+impl<Delay: Into<StdDuration>> SendAsyncDelayExecutor<Delay> for SmolSleep {
+    fn delay_by(&self, delay: Delay) -> impl Future<Output = ()> + Send {
+        let timer = async_io::Timer::after(scaled(delay));
+        async move {
+            timer.await;
+        }
+    }
+
+    fn delay_since(
+        &self,
+        delay: Delay,
+        since: Instant,
+        clock: &dyn Clock,
+    ) -> impl Future<Output = ()> + Send {
+        let timer = async_io::Timer::after(remaining(scaled(delay), since, clock));
+        async move {
+            timer.await;
+        }
+    }
+}
+
+/// Sleeps through `futures-timer`, which does not depend on any particular runtime.
+#[derive(Debug, Clone, Copy)]
+#[cfg(feature = "async-futures-timer")]
+pub struct FuturesTimerSleep;
+
+#[cfg(feature = "async-futures-timer")]
+impl<Delay: Into<StdDuration>> AsyncDelayExecutor<Delay> for FuturesTimerSleep {
+    async fn delay_by(&self, delay: Delay) {
+        futures_timer::Delay::new(scaled(delay)).await
+    }
+
+    async fn delay_since(&self, delay: Delay, since: Instant, clock: &dyn Clock) {
+        futures_timer::Delay::new(remaining(scaled(delay), since, clock)).await
+    }
+}
+
+#[cfg(feature = "async-futures-timer")]
+impl<Delay: Into<StdDuration>> SendAsyncDelayExecutor<Delay> for FuturesTimerSleep {
+    fn delay_by(&self, delay: Delay) -> impl Future<Output = ()> + Send {
+        futures_timer::Delay::new(scaled(delay))
+    }
+
+    fn delay_since(
+        &self,
+        delay: Delay,
+        since: Instant,
+        clock: &dyn Clock,
+    ) -> impl Future<Output = ()> + Send {
+        futures_timer::Delay::new(remaining(scaled(delay), since, clock))
+    }
+}
+
+/// The executor used by `retry_async`, `retry_async_send` and `RetryPolicy::retry_async`.
+///
+/// Selected by the enabled runtime features, in this order: `async-tokio`, `async-std`, `async-smol`,
+/// `async-futures-timer`.
+#[cfg(feature = "async-tokio")]
+pub use TokioSleep as DefaultAsyncSleep;
+
+/// The executor used by `retry_async`, `retry_async_send` and `RetryPolicy::retry_async`.
+///
+/// Selected by the enabled runtime features, in this order: `async-tokio`, `async-std`, `async-smol`,
+/// `async-futures-timer`.
+#[cfg(all(feature = "async-std", not(feature = "async-tokio")))]
+pub use AsyncStdSleep as DefaultAsyncSleep;
+
+/// The executor used by `retry_async`, `retry_async_send` and `RetryPolicy::retry_async`.
+///
+/// Selected by the enabled runtime features, in this order: `async-tokio`, `async-std`, `async-smol`,
+/// `async-futures-timer`.
+#[cfg(all(
+    feature = "async-smol",
+    not(any(feature = "async-tokio", feature = "async-std"))
+))]
+pub use SmolSleep as DefaultAsyncSleep;
+
+/// The executor used by `retry_async`, `retry_async_send` and `RetryPolicy::retry_async`.
+///
+/// Selected by the enabled runtime features, in this order: `async-tokio`, `async-std`, `async-smol`,
+/// `async-futures-timer`.
+#[cfg(all(
+    feature = "async-futures-timer",
+    not(any(feature = "async-tokio", feature = "async-std", feature = "async-smol"))
+))]
+pub use FuturesTimerSleep as DefaultAsyncSleep;
+
+/// Placeholder of the executor used by `retry_async` and `retry_async_send` when no runtime feature (`async-tokio`,
+/// `async-std`, `async-smol`, `async-futures-timer`) is enabled.
+///
+/// It cannot sleep, so an executor must be chosen through `with_executor`, e.g.
+/// `retry_async(..).with_executor(MyRuntimeSleep).delayed_by(..)`.
+#[derive(Debug, Clone, Copy)]
+#[cfg(all(
+    feature = "async",
+    not(any(
+        feature = "async-tokio",
+        feature = "async-std",
+        feature = "async-smol",
+        feature = "async-futures-timer"
+    ))
+))]
+pub struct DefaultAsyncSleep;
+
+/// Adapts an executor sleeping for `StdDuration`s to any delay type implementing `ToStdDuration`,
+/// e.g. `time::Duration` (feature `time`) or `chrono::TimeDelta` (feature `chrono`).
+#[derive(Debug, Clone, Copy)]
+pub struct ConvertDelay<Exec>(pub Exec);
+
+impl<Delay: ToStdDuration, Exec: DelayExecutor<StdDuration>> DelayExecutor<Delay>
+    for ConvertDelay<Exec>
+{
+    fn delay_by(&self, delay: Delay) {
+        self.0.delay_by(delay.to_std_duration())
+    }
+
+    fn delay_since(&self, delay: Delay, since: Instant, clock: &dyn Clock) {
+        self.0.delay_since(delay.to_std_duration(), since, clock)
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.0.is_cancelled()
+    }
+}
+
+#[cfg(feature = "async")]
+impl<Delay: ToStdDuration, Exec: AsyncDelayExecutor<StdDuration>> AsyncDelayExecutor<Delay>
+    for ConvertDelay<Exec>
+{
+    async fn delay_by(&self, delay: Delay) {
+        self.0.delay_by(delay.to_std_duration()).await
+    }
+
+    async fn delay_since(&self, delay: Delay, since: Instant, clock: &dyn Clock) {
+        self.0
+            .delay_since(delay.to_std_duration(), since, clock)
+            .await
+    }
+}
+
+#[cfg(feature = "async")]
+impl<Delay: ToStdDuration, Exec: SendAsyncDelayExecutor<StdDuration>> SendAsyncDelayExecutor<Delay>
+    for ConvertDelay<Exec>
+{
+    fn delay_by(&self, delay: Delay) -> impl Future<Output = ()> + Send {
+        self.0.delay_by(delay.to_std_duration())
+    }
+
+    fn delay_since(
+        &self,
+        delay: Delay,
+        since: Instant,
+        clock: &dyn Clock,
+    ) -> impl Future<Output = ()> + Send {
+        self.0.delay_since(delay.to_std_duration(), since, clock)
+    }
+}
+
+#[cfg(all(test, feature = "async"))]
+mod test {
+    use super::*;
+    use assertr::prelude::*;
+
+    #[test]
+    fn dropped_cancelled_futures_release_their_wakers() {
+        let handle = CancelHandle::default();
+        let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+
+        let mut first = std::pin::pin!(handle.cancelled());
+        let mut second = Box::pin(handle.cancelled());
+        assert_that(first.as_mut().poll(&mut context).is_pending()).is_true();
+        assert_that(second.as_mut().poll(&mut context).is_pending()).is_true();
+        assert_that(second.as_mut().poll(&mut context).is_pending()).is_true();
+        assert_that(lock(&handle.state.0).wakers.len()).is_equal_to(2);
+
+        drop(second);
+        assert_that(lock(&handle.state.0).wakers.len()).is_equal_to(1);
+
+        handle.cancel();
+        assert_that(first.as_mut().poll(&mut context).is_ready()).is_true();
+        assert_that(lock(&handle.state.0).wakers.len()).is_equal_to(0);
     }
 }
@@ -1,3 +1,6 @@
+use crate::StdDuration;
+use crate::delay::Preview;
+use crate::duration::ToStdDuration;
 use crate::tracked_iterator::FiniteIterator;
 use std::fmt::Debug;
 
@@ -5,8 +8,37 @@ use std::fmt::Debug;
 /// A `FiniteIterator` is enforced, as we want users to always specify a concrete number of retries!
 pub trait DelayStrategy<Delay>: Debug {
     fn next_delay(&mut self) -> Option<Delay>;
+
+    /// Advances the strategy like `next_delay`, but yields the smallest and largest delay the strategy could have
+    /// chosen instead of a concrete one. Only randomized strategies (see `Jittered`) need to override this.
+    fn next_delay_bounds(&mut self) -> Option<(Delay, Delay)>
+    where
+        Delay: Clone,
+    {
+        self.next_delay().map(|delay| (delay.clone(), delay))
+    }
+
+    /// Erases the type of this strategy, e.g. to select a strategy at runtime.
+    fn boxed(self) -> BoxedDelayStrategy<Delay>
+    where
+        Self: Sized + Send + Sync + 'static,
+    {
+        Box::new(self)
+    }
+
+    /// Computes the delays this strategy would apply (at most `Preview::MAX_RETRIES`), without consuming it.
+    fn preview(&self) -> Preview
+    where
+        Self: Clone + Sized,
+        Delay: Clone + ToStdDuration,
+    {
+        Preview::of(self.clone())
+    }
 }
 
+/// A type-erased delay strategy, e.g. chosen at runtime from configuration and stored in a struct.
+pub type BoxedDelayStrategy<Delay = StdDuration> = Box<dyn DelayStrategy<Delay> + Send + Sync>;
+
 impl<Delay, I> DelayStrategy<Delay> for FiniteIterator<I>
 where
     I: Iterator<Item = Delay> + Debug,
@@ -15,3 +47,19 @@ where
         self.next()
     }
 }
+
+impl<Delay, S> DelayStrategy<Delay> for Box<S>
+where
+    S: DelayStrategy<Delay> + ?Sized,
+{
+    fn next_delay(&mut self) -> Option<Delay> {
+        (**self).next_delay()
+    }
+
+    fn next_delay_bounds(&mut self) -> Option<(Delay, Delay)>
+    where
+        Delay: Clone,
+    {
+        (**self).next_delay_bounds()
+    }
+}
@@ -31,3 +31,138 @@ impl IntoStdDuration for u64 {
         StdDuration::from_secs(self)
     }
 }
+
+/// Delay types the provided executors can sleep for.
+///
+/// Implemented for `StdDuration` and, with the `time` and `chrono` features, for `time::Duration` and
+/// `chrono::TimeDelta`. Negative durations are treated as zero.
+pub trait ToStdDuration {
+    fn to_std_duration(&self) -> StdDuration;
+}
+
+impl ToStdDuration for StdDuration {
+    fn to_std_duration(&self) -> StdDuration {
+        *self
+    }
+}
+
+#[cfg(feature = "time")]
+impl ToStdDuration for time::Duration {
+    fn to_std_duration(&self) -> StdDuration {
+        StdDuration::try_from(*self).unwrap_or(StdDuration::ZERO)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ToStdDuration for chrono::TimeDelta {
+    fn to_std_duration(&self) -> StdDuration {
+        self.to_std().unwrap_or(StdDuration::ZERO)
+    }
+}
+
+/// Like `IntoStdDuration`, but creating `time::Duration`s (feature `time`). Saturates at `time::Duration::MAX`.
+#[cfg(feature = "time")]
+pub trait IntoTimeDuration {
+    #[must_use]
+    fn nanos(self) -> time::Duration;
+
+    #[must_use]
+    fn micros(self) -> time::Duration;
+
+    #[must_use]
+    fn millis(self) -> time::Duration;
+
+    #[must_use]
+    fn secs(self) -> time::Duration;
+}
+
+#[cfg(feature = "time")]
+impl IntoTimeDuration for u64 {
+    fn nanos(self) -> time::Duration {
+        to_time_duration(IntoStdDuration::nanos(self))
+    }
+
+    fn micros(self) -> time::Duration {
+        to_time_duration(IntoStdDuration::micros(self))
+    }
+
+    fn millis(self) -> time::Duration {
+        to_time_duration(IntoStdDuration::millis(self))
+    }
+
+    fn secs(self) -> time::Duration {
+        to_time_duration(IntoStdDuration::secs(self))
+    }
+}
+
+#[cfg(feature = "time")]
+fn to_time_duration(duration: StdDuration) -> time::Duration {
+    time::Duration::try_from(duration).unwrap_or(time::Duration::MAX)
+}
+
+/// Like `IntoStdDuration`, but creating `chrono::TimeDelta`s (feature `chrono`). Saturates at `TimeDelta::MAX`.
+#[cfg(feature = "chrono")]
+pub trait IntoTimeDelta {
+    #[must_use]
+    fn nanos(self) -> chrono::TimeDelta;
+
+    #[must_use]
+    fn micros(self) -> chrono::TimeDelta;
+
+    #[must_use]
+    fn millis(self) -> chrono::TimeDelta;
+
+    #[must_use]
+    fn secs(self) -> chrono::TimeDelta;
+}
+
+#[cfg(feature = "chrono")]
+impl IntoTimeDelta for u64 {
+    fn nanos(self) -> chrono::TimeDelta {
+        to_time_delta(IntoStdDuration::nanos(self))
+    }
+
+    fn micros(self) -> chrono::TimeDelta {
+        to_time_delta(IntoStdDuration::micros(self))
+    }
+
+    fn millis(self) -> chrono::TimeDelta {
+        to_time_delta(IntoStdDuration::millis(self))
+    }
+
+    fn secs(self) -> chrono::TimeDelta {
+        to_time_delta(IntoStdDuration::secs(self))
+    }
+}
+
+#[cfg(feature = "chrono")]
+fn to_time_delta(duration: StdDuration) -> chrono::TimeDelta {
+    chrono::TimeDelta::from_std(duration).unwrap_or(chrono::TimeDelta::MAX)
+}
+
+pub(crate) mod human;
+
+#[cfg(all(test, any(feature = "time", feature = "chrono")))]
+mod test {
+    use super::*;
+    use assertr::prelude::*;
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_durations_convert_to_std_durations() {
+        assert_that(IntoTimeDuration::millis(1500).to_std_duration())
+            .is_equal_to(IntoStdDuration::millis(1500));
+        assert_that(time::Duration::seconds(-1).to_std_duration()).is_equal_to(StdDuration::ZERO);
+        assert_that(IntoTimeDuration::secs(u64::MAX)).is_equal_to(time::Duration::MAX);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_time_deltas_convert_to_std_durations() {
+        assert_that(IntoTimeDelta::millis(1500).to_std_duration())
+            .is_equal_to(IntoStdDuration::millis(1500));
+        assert_that(chrono::TimeDelta::seconds(-1).to_std_duration())
+            .is_equal_to(StdDuration::ZERO);
+        assert_that(IntoTimeDelta::secs(u64::MAX)).is_equal_to(chrono::TimeDelta::MAX);
+    }
+}
@@ -0,0 +1,198 @@
+use crate::StdDuration;
+
+const NANOS_PER_UNIT: [(&str, u128); 7] = [
+    ("h", 3_600_000_000_000),
+    ("m", 60_000_000_000),
+    ("s", 1_000_000_000),
+    ("ms", 1_000_000),
+    ("us", 1_000),
+    ("µs", 1_000),
+    ("ns", 1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ParseDurationError {
+    Empty,
+    InvalidNumber(String),
+    MissingUnit,
+    UnknownUnit(String),
+    Overflow,
+}
+
+impl std::fmt::Display for ParseDurationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseDurationError::Empty => write!(f, "expected a duration like \"250ms\""),
+            ParseDurationError::InvalidNumber(number) => write!(f, "invalid number \"{number}\""),
+            ParseDurationError::MissingUnit => {
+                write!(f, "missing unit (expected one of h, m, s, ms, us, ns)")
+            }
+            ParseDurationError::UnknownUnit(unit) => write!(
+                f,
+                "unknown unit \"{unit}\" (expected one of h, m, s, ms, us, ns)"
+            ),
+            ParseDurationError::Overflow => write!(f, "duration is too large"),
+        }
+    }
+}
+
+impl std::error::Error for ParseDurationError {}
+
+/// Parses human-friendly durations like `"250ms"`, `"1.5s"` or `"2m"`.
+pub(crate) fn parse_duration(input: &str) -> Result<StdDuration, ParseDurationError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(ParseDurationError::Empty);
+    }
+
+    let number_len = input
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(input.len());
+    let (number, unit) = input.split_at(number_len);
+    let unit = unit.trim_start();
+
+    let (integer, fraction) = number.split_once('.').unwrap_or((number, ""));
+    if integer.is_empty() && fraction.is_empty() || fraction.contains('.') {
+        return Err(ParseDurationError::InvalidNumber(number.to_owned()));
+    }
+    if unit.is_empty() {
+        return Err(ParseDurationError::MissingUnit);
+    }
+    let nanos_per_unit = NANOS_PER_UNIT
+        .iter()
+        .find(|(name, _)| *name == unit)
+        .map(|(_, nanos)| *nanos)
+        .ok_or_else(|| ParseDurationError::UnknownUnit(unit.to_owned()))?;
+
+    let integer: u128 = match integer {
+        "" => 0,
+        integer => integer.parse().map_err(|_| ParseDurationError::Overflow)?,
+    };
+    let mut nanos = integer
+        .checked_mul(nanos_per_unit)
+        .ok_or(ParseDurationError::Overflow)?;
+    let mut scale = nanos_per_unit;
+    for digit in fraction.chars() {
+        let digit = u128::from(digit.to_digit(10).unwrap_or_default());
+        scale /= 10;
+        nanos += digit * scale;
+    }
+
+    let secs = u64::try_from(nanos / 1_000_000_000).map_err(|_| ParseDurationError::Overflow)?;
+    let subsec_nanos = (nanos % 1_000_000_000) as u32;
+    Ok(StdDuration::new(secs, subsec_nanos))
+}
+
+/// Formats a duration using the largest unit representing it exactly, e.g. `"250ms"` or `"2m"`.
+pub(crate) fn format_duration(duration: StdDuration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos == 0 {
+        return "0s".to_owned();
+    }
+    let (unit, nanos_per_unit) = NANOS_PER_UNIT
+        .iter()
+        .filter(|(name, _)| *name != "µs")
+        .find(|(_, nanos_per_unit)| nanos % nanos_per_unit == 0)
+        .copied()
+        .unwrap_or(("ns", 1));
+    format!("{}{unit}", nanos / nanos_per_unit)
+}
+
+/// (De)serializes durations in their human-friendly representation, e.g. `"250ms"`.
+#[cfg(feature = "serde")]
+pub(crate) mod string {
+    use super::{StdDuration, format_duration, parse_duration};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub(crate) fn serialize<S: Serializer>(
+        duration: &StdDuration,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format_duration(*duration))
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<StdDuration, D::Error> {
+        let input = String::deserialize(deserializer)?;
+        parse_duration(&input).map_err(serde::de::Error::custom)
+    }
+
+    /// Variant of the surrounding module for optional durations.
+    pub(crate) mod option {
+        use super::{StdDuration, format_duration, parse_duration};
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        pub(crate) fn serialize<S: Serializer>(
+            duration: &Option<StdDuration>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match duration {
+                Some(duration) => serializer.serialize_some(&format_duration(*duration)),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<StdDuration>, D::Error> {
+            Option::<String>::deserialize(deserializer)?
+                .map(|input| parse_duration(&input).map_err(serde::de::Error::custom))
+                .transpose()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::IntoStdDuration;
+    use assertr::prelude::*;
+
+    #[test]
+    fn parses_human_friendly_durations() {
+        assert_that(parse_duration("250ms"))
+            .is_ok()
+            .is_equal_to(250.millis());
+        assert_that(parse_duration("1.5s"))
+            .is_ok()
+            .is_equal_to(1500.millis());
+        assert_that(parse_duration("2m"))
+            .is_ok()
+            .is_equal_to(120.secs());
+        assert_that(parse_duration("1h"))
+            .is_ok()
+            .is_equal_to(3600.secs());
+        assert_that(parse_duration("10 us"))
+            .is_ok()
+            .is_equal_to(10.micros());
+        assert_that(parse_duration(".5ms"))
+            .is_ok()
+            .is_equal_to(500.micros());
+    }
+
+    #[test]
+    fn rejects_malformed_durations() {
+        assert_that(parse_duration(""))
+            .is_err()
+            .is_equal_to(ParseDurationError::Empty);
+        assert_that(parse_duration("250"))
+            .is_err()
+            .is_equal_to(ParseDurationError::MissingUnit);
+        assert_that(parse_duration("250days"))
+            .is_err()
+            .is_equal_to(ParseDurationError::UnknownUnit("days".to_owned()));
+        assert_that(parse_duration("1.2.3s"))
+            .is_err()
+            .is_equal_to(ParseDurationError::InvalidNumber("1.2.3".to_owned()));
+    }
+
+    #[test]
+    fn formats_durations_using_the_largest_exact_unit() {
+        assert_that(format_duration(StdDuration::ZERO)).is_equal_to("0s".to_owned());
+        assert_that(format_duration(250.millis())).is_equal_to("250ms".to_owned());
+        assert_that(format_duration(1500.millis())).is_equal_to("1500ms".to_owned());
+        assert_that(format_duration(120.secs())).is_equal_to("2m".to_owned());
+        assert_that(format_duration(7.nanos())).is_equal_to("7ns".to_owned());
+    }
+}
@@ -1,11 +1,25 @@
 pub trait NeedsRetry {
     fn needs_retry(&self) -> bool;
+
+    /// Name of the type of a failed output, reported as `error.type` (feature `opentelemetry`).
+    ///
+    /// Defaults to the name of the output type itself. `Result<T, E>` reports the name of `E`.
+    fn failure_type() -> &'static str
+    where
+        Self: Sized,
+    {
+        std::any::type_name::<Self>()
+    }
 }
 
 impl<T, E> NeedsRetry for Result<T, E> {
     fn needs_retry(&self) -> bool {
         self.is_err()
     }
+
+    fn failure_type() -> &'static str {
+        std::any::type_name::<E>()
+    }
 }
 
 impl<T> NeedsRetry for Option<T> {
@@ -37,6 +51,11 @@ mod test {
         assert_that(result.needs_retry()).is_true();
     }
 
+    #[test]
+    fn result_reports_its_error_type_as_failure_type() {
+        assert_that(<Result<(), u8>>::failure_type()).is_equal_to("u8");
+    }
+
     #[test]
     fn option_does_not_need_retry_when_some() {
         let option: Option<()> = Some(());
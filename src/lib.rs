@@ -34,8 +34,10 @@
 //!
 //! ```rust
 //! use assertr::prelude::*;
+//! # #[cfg(feature = "async")]
 //! use try_again::{delay, retry_async, IntoStdDuration};
 //!
+//! # #[cfg(any(feature = "async-tokio", feature = "async-std", feature = "async-smol", feature = "async-futures-timer"))]
 //! async fn do_smth() {
 //!     async fn fallible_operation() -> Result<(), ()> {
 //!         Ok(())
@@ -59,6 +61,20 @@
 //! - `Fixed`: A static delay.
 //! - `ExponentialBackoff`: An exponentially increasing delay
 //!
+//! Each of them can be wrapped in `Jittered` to randomize the delays (see `Jitter`).
+//!
+//! Every strategy implementing `Clone` (as well as `Schedule` and `ScheduleSpec`) can be previewed without sleeping.
+//! `preview()` lists all delays and their cumulative offsets (as ranges for jittered strategies) and displays like
+//! `attempts at t=0, 100ms, 300ms, 700ms; worst-case total 700ms`. The strategies themselves display a description in
+//! words, e.g. `exponential backoff starting at 100ms, doubling on every retry, capped at 5s`.
+//!
+//! Constructors accept any configuration. To reject backoffs which are most likely unintended, like one capped below its
+//! initial delay or starting at zero, use the `try_` constructors (e.g. `ExponentialBackoff::try_capped_at`) or
+//! `validate()` on the backoff. `validate()` on `Schedule` and `ScheduleSpec` checks all of their backoffs and additionally
+//! rejects schedules without any retries. `validate_within` also rejects schedules whose delays may add up to more than a
+//! given limit. The `take` of the raw strategies is not validated, so `delay::Fixed::of(..).take(0)` is accepted and
+//! never retries. The `registry` validates all loaded policies.
+//!
 //! All work with `std::time::Duration`, re-exposed as `StdDuration`. The `IntoStdDuration` can be used for a fluent syntax
 //! when defining durations, like in
 //!
@@ -66,52 +82,231 @@
 //!
 //! delay::Fixed::of(250.millis())
 //!
+//! With the `time` and `chrono` features, `time::Duration` and `chrono::TimeDelta` can be used as well: The strategy
+//! constructors accept them, `delayed_by` accepts strategies yielding them (any delay type implementing `ToStdDuration`)
+//! and `IntoTimeDuration` / `IntoTimeDelta` provide the same fluent syntax. Negative durations are treated as zero.
+//!
 //! ### Delay executors
 //!
 //! The standard `retry` and `retry_async` functions have the following default behavior:
 //!
 //! - `retry` puts the current thread to sleep between retries (through the provided `ThreadSleep` executor).
-//! - `retry_async` instructs the tokio runtime to sleep between retries (through the provided `TokioSleep` executor,
-//!   requires the `async-tokio` feature (enabled by default)).
+//! - `retry_async` instructs the async runtime to sleep between retries (through the `DefaultAsyncSleep` executor).
+//!
+//! `DefaultAsyncSleep` is selected by the enabled runtime features, in this order:
+//!
+//! | Feature                            | Executor            | Sleeps through                            |
+//! |------------------------------------|---------------------|-------------------------------------------|
+//! | `async-tokio` (enabled by default) | `TokioSleep`        | `tokio::time::sleep`                      |
+//! | `async-std`                        | `AsyncStdSleep`     | `async_std::task::sleep`                  |
+//! | `async-smol`                       | `SmolSleep`         | `async_io::Timer` (the reactor of `smol`) |
+//! | `async-futures-timer`              | `FuturesTimerSleep` | `futures_timer::Delay` (runtime-agnostic) |
+//!
+//! To use another runtime than tokio by default, disable the default features, e.g.
+//! `try-again = { version = "..", default-features = false, features = ["async-smol", "tracing"] }`.
+//!
+//! The fluent `retry_async` and `retry_async_send` functions accept any other executor through `with_executor`, e.g.
+//! `retry_async(..).with_executor(MySleep).delayed_by(..)`. This step is required when only the `async` feature is
+//! enabled, as no default executor is available then.
 //!
 //! The `retry_with_options` and `retry_async_with_options` functions can be used to overwrite the standard behavior
-//! with any executor type implementing the `DelayExecutor` trait.
+//! with any executor type implementing the `DelayExecutor` trait. Their options are created through a builder, e.g.
+//! `RetryOptions::builder().strategy(..).executor(..).build()`. That way, support for further asynchronous runtimes can be
+//! provided.
+//!
+//! To select strategies and executors at runtime (e.g. from configuration) and store options without being generic over
+//! them, use `BoxedDelayStrategy` (see `DelayStrategy::boxed`), `BoxedDelayExecutor` and `DynAsyncDelayExecutor`, which
+//! wraps any `AsyncDelayExecutor` in a dyn-compatible type.
+//!
+//! The future returned by `retry_async` is not guaranteed to be `Send`, as neither `AsyncFn` closures nor
+//! `AsyncDelayExecutor` can promise `Send` futures. To spawn a retry loop onto a multi-threaded runtime, use
+//! `retry_async_send` (or `retry_async_send_with_options` together with an executor implementing
+//! `SendAsyncDelayExecutor`), e.g. `tokio::spawn(retry_async_send(|| async { .. }).delayed_by(..))`.
+//!
+//! By default, a delay starts once the attempt finished, so the schedule drifts by the runtime of every attempt. To start
+//! attempts on a fixed schedule instead (e.g. really every 5s), anchor delays at the start of each attempt through
+//! `.delay_anchor(DelayAnchor::AttemptStart)` of the options builders. The provided executors then only sleep for the
+//! part of the delay left and retry immediately when an attempt outlasted its delay. The time passed is measured through
+//! the `clock` of the options, which `delay_since` receives, so pair `TokioSleep` with the `TokioClock` when pausing
+//! tokio's time. Custom executors support deadlines by implementing `delay_since`, otherwise they sleep for the full
+//! delay.
+//!
+//! ### Cancellation
+//!
+//! `retry_async_with_cancellation` stops an asynchronous retry loop as soon as a `CancelSignal` completes, e.g. during a
+//! graceful shutdown. Any future can serve as the signal, like `CancellationToken::cancelled()` of `tokio-util`. Pending
+//! delays are interrupted immediately, running attempts only when configured through `interrupting_attempts`. A cancelled
+//! loop returns `Err(Cancelled { attempts, last_output })`, distinguishing it from a loop which gave up.
+//!
+//! Synchronous retry loops can be cancelled through the `CancellableThreadSleep` executor, which sleeps like
+//! `ThreadSleep` but is woken up early when its `CancelHandle` is cancelled from another thread. `retry_with_cancellation`
+//! then reports `Err(Cancelled { .. })`, while `retry_with_options` simply stops retrying and returns the last output.
+//!
+//! With the `signals` feature (Unix only), `SIGINT` (Ctrl-C) and `SIGTERM` stop retry loops gracefully instead of killing
+//! the process mid-attempt: `signals::cancellable_thread_sleep()` and `signals::cancel_signal()` install a handler whose
+//! first signal cancels all retry loops using them. A second signal terminates the process as usual. A `CancelHandle` can
+//! also be shared between synchronous and asynchronous loops through `CancelSignal::on(handle.cancelled())`.
+//!
+//! Note that the handler is process-wide and never removed: Once installed, the first Ctrl-C only cancels the handle,
+//! even when no retry loop is running, and a second one is required to terminate the process. Use these functions only
+//! in processes which check `CancelHandle::is_cancelled` to shut down.
+//!
+//! ### Retry policies
+//!
+//! Delay strategies are consumed by the retry loop they are used in. To define how an operation is retried once and reuse
+//! that definition at every call site, describe the delays as a `delay::Schedule` and wrap it in a `RetryPolicy`.
+//! A policy creates a fresh delay strategy for every call to `retry` (or `retry_async`), can be constructed in `const`
+//! contexts and may carry a custom retry predicate set through `retry_if`.
+//!
+//! With the `registry` feature, policies can be defined once in a TOML file and looked up by name through `Policies`
+//! (or any `PolicyRegistry`), e.g. `Policies::get("db")`. Invalid entries are reported when loading the file.
+//! `watch_toml_file` keeps reloading the file whenever it changes, so the next retry loop picks up the new policy, while
+//! retry loops already running keep their schedule.
+//!
+//! With the `serde` feature, the strategies of the `delay` module, `delay::Schedule` and `RetryPolicy` implement
+//! `Serialize` and `Deserialize`. Durations are represented as human-friendly strings like `"250ms"` or `"1.5s"`, and
+//! schedules as internally tagged values, e.g. `{ "type": "fixed", "delay": "250ms", "retries": 3 }`. A schedule can also
+//! be deserialized directly into a `delay::ScheduleStrategy`, ready to be passed to `delayed_by`.
+//!
+//! Schedules can also be written in a compact textual notation (e.g. for CLI flags or environment variables) and parsed
+//! into a `delay::ScheduleSpec`, like `exp(100ms, x2, cap=5s, tries=8, jitter=full)` or `fixed(1s)*5 then exp(2s)*3`.
+//! `delay::parse_strategy` parses such a schedule directly into a boxed `DelayStrategy<StdDuration>`.
+//! A spec converts into a `delay::Schedule`, which covers jitter and sequences (`{ "type": "sequence", "segments": [..] }`)
+//! as well, so the same schedules can back a `RetryPolicy` or be defined in the registry.
+//!
+//! ### Overrides for tests
+//!
+//! Production schedules may wait for seconds, slowing down tests considerably. The `overrides` module allows scaling all
+//! delays (`TRY_AGAIN_TIME_SCALE=0.01` or `overrides::set_time_scale(0.01)`) and limiting the number of attempts of all
+//! retry loops (`TRY_AGAIN_MAX_ATTEMPTS=2` or `overrides::set_max_attempts(Some(2))`) without touching any call site.
+//!
+//! To assert the exact schedule instead, enable the `test-util` feature and pass a `test_util::VirtualClock` as the
+//! executor. It advances simulated time instead of sleeping and records every requested delay, so tests can check
+//! `clock.recorded_delays()` for both the sync and async retry loops.
+//!
+//! Flaky operations can be faked through `test_util::ScriptedOperation`, returning a predefined sequence of outputs
+//! (e.g. `[Err(Timeout), Err(Timeout), Ok(42)]`) through `as_fn` or `as_async_fn`. It counts its calls and panics when
+//! called more often than scripted.
+//!
+//! Retry loops measure elapsed time (e.g. the `elapsed_ms` of attempts and the `try_again_elapsed_seconds` metric)
+//! through the `clock::Clock` of their options instead of calling `Instant::now()` directly. It defaults to the
+//! `SystemClock` and can be replaced through the `clock` builder step, e.g. with a `TokioClock` honoring
+//! `tokio::time::pause`, a `ManualClock` only moving when advanced, or a `VirtualClock` reporting simulated time.
+//!
+//! ### Logging
+//!
+//! Retries and give-ups are reported through `tracing` (feature `tracing`, enabled by default) and/or the `log` crate
+//! (feature `log`). Disable default features to drop the `tracing` dependency.
+//!
+//! By default, retries are reported at `Debug`, give-ups at `Error` and cancellations at `Info` level. Use
+//! `set_default_log_levels` to change this globally or `with_log_levels` to change it for a single retry loop, like in
+//!
+//! ```rust
+//! use try_again::{delay, retry, LogLevel, LogLevels};
+//!
+//! fn operation() -> Result<(), ()> {
+//!     Ok(())
+//! }
+//!
+//! let outcome = retry(operation)
+//!     .with_log_levels(LogLevels::new(LogLevel::Trace, LogLevel::Warn))
+//!     .delayed_by(delay::None.take(3));
+//! ```
+//!
+//! With the `tracing` feature, every attempt is wrapped in an `attempt` span (fields `attempt`, `delay_ms`, `outcome` and
+//! `elapsed_ms`), and the total number of attempts is recorded as `attempts` on the span of the retry loop.
+//!
+//! ### Metrics
+//!
+//! With the `metrics` feature, every retry loop records the following metrics through the `metrics` facade, labeled with
+//! `operation`, the name given through `named` (or `RetryOptions::operation_name`):
+//!
+//! - `try_again_attempts_total` (counter)
+//! - `try_again_retries_total` (counter)
+//! - `try_again_give_ups_total` (counter)
+//! - `try_again_attempts_per_call` (histogram)
+//! - `try_again_elapsed_seconds` (histogram)
 //!
-//! That way, support for `async_std` or other asynchronous runtimes could be provided.
+//! ### OpenTelemetry
+//!
+//! With the `opentelemetry` feature, every retry adds a `retry` event (attributes `retry.attempt`, `retry.delay` and
+//! `error.type`) to the active OpenTelemetry span. Giving up adds a `retry.give_up` event and sets the span status to
+//! `Error`.
 
 #![forbid(unsafe_code)]
 #![deny(clippy::unwrap_used)]
 
+mod cancellation;
+pub mod clock;
 pub mod delay;
 pub mod delay_executor;
 pub mod delay_strategy;
 mod duration;
 mod fallible;
+mod logging;
+mod open_telemetry;
+mod options;
+pub mod overrides;
+mod policy;
+#[cfg(feature = "registry")]
+mod registry;
+mod retry_metrics;
+#[cfg(all(unix, feature = "signals"))]
+pub mod signals;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 mod tracked_iterator;
 
+use std::borrow::Cow;
 use std::fmt::Debug;
-use std::marker::PhantomData;
+#[cfg(feature = "async")]
+use std::pin::{Pin, pin};
+#[cfg(feature = "async")]
+use std::time::Instant;
 
+use crate::clock::Clock;
 #[cfg(feature = "async")]
-use crate::delay_executor::AsyncDelayExecutor;
-use crate::delay_executor::DelayExecutor;
-use crate::delay_executor::ThreadSleep;
-#[cfg(feature = "async-tokio")]
-use crate::delay_executor::TokioSleep;
+use crate::delay_executor::DefaultAsyncSleep;
+#[cfg(feature = "async")]
+use crate::delay_executor::{AsyncDelayExecutor, SendAsyncDelayExecutor};
+use crate::delay_executor::{ConvertDelay, DelayExecutor, ThreadSleep};
 use crate::delay_strategy::DelayStrategy;
+use crate::retry_metrics::RetryMetrics;
 
+#[cfg(feature = "async")]
+pub use cancellation::CancelSignal;
+pub use cancellation::Cancelled;
 pub use duration::IntoStdDuration;
+#[cfg(feature = "chrono")]
+pub use duration::IntoTimeDelta;
+#[cfg(feature = "time")]
+pub use duration::IntoTimeDuration;
 pub use duration::StdDuration;
+pub use duration::ToStdDuration;
 pub use fallible::NeedsRetry;
+pub use logging::{LogLevel, LogLevels, default_log_levels, set_default_log_levels};
+pub use options::{DelayAnchor, RetryOptions, RetryOptionsBuilder};
+#[cfg(feature = "async")]
+pub use options::{RetryAsyncOptions, RetryAsyncOptionsBuilder};
+pub use policy::{ByNeedsRetry, RetryPolicy, RetryPredicate};
+#[cfg(feature = "registry")]
+pub use registry::{InvalidPolicy, Policies, PolicyRegistry, PolicyWatcher, RegistryError};
 
-#[tracing::instrument(level = "debug", name = "retry", skip(operation))]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", name = "retry", skip(operation))
+)]
 #[must_use = "Call `delayed_by` on the returned value to complete the retry strategy configuration."]
 pub fn retry<Out, Op>(operation: Op) -> NeedsDelayStrategy<Out, Op>
 where
     Out: NeedsRetry + Debug,
     Op: Fn() -> Out,
 {
-    NeedsDelayStrategy { operation }
+    NeedsDelayStrategy {
+        operation,
+        operation_name: None,
+        log_levels: None,
+    }
 }
 
 pub struct NeedsDelayStrategy<Out, Op>
@@ -120,6 +315,8 @@ where
     Op: Fn() -> Out,
 {
     operation: Op,
+    operation_name: Option<Cow<'static, str>>,
+    log_levels: Option<LogLevels>,
 }
 
 impl<Out, Op> NeedsDelayStrategy<Out, Op>
@@ -127,37 +324,97 @@ where
     Out: NeedsRetry + Debug,
     Op: Fn() -> Out,
 {
-    pub fn delayed_by<DelayStrat>(self, delay: DelayStrat) -> Out
+    /// Names the retried operation. The name is used to label the recorded metrics (feature `metrics`).
+    pub fn named(mut self, operation_name: impl Into<Cow<'static, str>>) -> Self {
+        self.operation_name = Some(operation_name.into());
+        self
+    }
+
+    /// Overwrites the globally configured log levels (see `set_default_log_levels`) for this retry loop.
+    pub fn with_log_levels(mut self, log_levels: LogLevels) -> Self {
+        self.log_levels = Some(log_levels);
+        self
+    }
+
+    /// Runs the retry loop, putting the current thread to sleep between attempts.
+    ///
+    /// Strategies may yield any delay type implementing `ToStdDuration`.
+    pub fn delayed_by<Delay, DelayStrat>(self, delay: DelayStrat) -> Out
     where
-        DelayStrat: DelayStrategy<StdDuration>,
+        Delay: ToStdDuration + Debug + Clone,
+        DelayStrat: DelayStrategy<Delay>,
     {
-        retry_with_options(
-            self.operation,
-            RetryOptions {
-                delay_strategy: delay,
-                delay_executor: ThreadSleep,
-                _marker: PhantomData,
-            },
-        )
+        let mut options = RetryOptions::builder()
+            .strategy(delay)
+            .executor(ConvertDelay(ThreadSleep))
+            .build();
+        options.operation_name = self.operation_name;
+        options.log_levels = self.log_levels;
+        retry_with_options(self.operation, options)
     }
 }
 
-#[derive(Debug)]
-pub struct RetryOptions<
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        name = "retry_with_options",
+        skip(operation),
+        fields(attempts = tracing::field::Empty)
+    )
+)]
+pub fn retry_with_options<Delay, DelayStrat, DelayExec, Out, Op>(
+    operation: Op,
+    options: RetryOptions<Delay, DelayStrat, DelayExec>,
+) -> Out
+where
     Delay: Debug + Clone,
-    DelayStrat: DelayStrategy<Delay>,
-    DelayExec: DelayExecutor<Delay>,
-> {
-    pub delay_strategy: DelayStrat,
-    pub delay_executor: DelayExec,
-    pub _marker: PhantomData<Delay>,
+    DelayStrat: DelayStrategy<Delay> + Debug,
+    DelayExec: DelayExecutor<Delay> + Debug,
+    Out: NeedsRetry + Debug,
+    Op: Fn() -> Out,
+{
+    match retry_loop(operation, options) {
+        Ok(out) => out,
+        Err(cancelled) => cancelled
+            .last_output
+            .unwrap_or_else(|| unreachable!("the first attempt is never cancelled")),
+    }
 }
 
-#[tracing::instrument(level = "debug", name = "retry_with_options", skip(operation))]
-pub fn retry_with_options<Delay, DelayStrat, DelayExec, Out, Op>(
+/// Like `retry_with_options`, but reports a cancelled executor (see `CancellableThreadSleep`) as `Cancelled`, carrying
+/// the output of the last attempt, instead of returning that output as is.
+///
+/// The executor is checked before and after every delay, so the first attempt always runs. A retry loop finishing on
+/// its own returns its output as `Ok`, even when it gave up.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        name = "retry_with_cancellation",
+        skip(operation),
+        fields(attempts = tracing::field::Empty)
+    )
+)]
+pub fn retry_with_cancellation<Delay, DelayStrat, DelayExec, Out, Op>(
+    operation: Op,
+    options: RetryOptions<Delay, DelayStrat, DelayExec>,
+) -> Result<Out, Cancelled<Out>>
+where
+    Delay: Debug + Clone,
+    DelayStrat: DelayStrategy<Delay> + Debug,
+    DelayExec: DelayExecutor<Delay> + Debug,
+    Out: NeedsRetry + Debug,
+    Op: Fn() -> Out,
+{
+    retry_loop(operation, options)
+}
+
+/// The retry loop shared by all synchronous entry points.
+fn retry_loop<Delay, DelayStrat, DelayExec, Out, Op>(
     operation: Op,
     mut options: RetryOptions<Delay, DelayStrat, DelayExec>,
-) -> Out
+) -> Result<Out, Cancelled<Out>>
 where
     Delay: Debug + Clone,
     DelayStrat: DelayStrategy<Delay> + Debug,
@@ -165,20 +422,53 @@ where
     Out: NeedsRetry + Debug,
     Op: Fn() -> Out,
 {
+    let log_levels = options.log_levels.unwrap_or_else(default_log_levels);
+    let clock = &*options.clock;
+    let metrics = RetryMetrics::start(options.operation_name.as_ref(), clock);
+    let max_attempts = overrides::max_attempts();
     let mut tries: usize = 1;
+    let mut last_wait: Option<StdDuration> = None;
     loop {
-        let out = operation();
-        match out.needs_retry() {
-            false => return out,
-            true => match options.delay_strategy.next_delay() {
+        let started_at = clock.now();
+        let attempt = logging::Attempt::start(tries, last_wait, clock);
+        metrics.attempt();
+        let out = attempt.run(&operation);
+        let needs_retry = out.needs_retry();
+        attempt.finish(needs_retry, clock);
+        match needs_retry {
+            false => {
+                logging::record_attempts(tries);
+                metrics.finish(tries, clock);
+                return Ok(out);
+            }
+            true => match next_delay(&mut options.delay_strategy, tries, max_attempts) {
+                Some(_) if options.delay_executor.is_cancelled() => {
+                    return Err(cancelled(tries, Some(out), log_levels, metrics, clock));
+                }
                 Some(delay) => {
-                    tracing::debug!(tries, delay = ?delay, "Operation was not successful. Waiting...");
-                    options.delay_executor.delay_by(delay.clone());
+                    logging::retrying(log_levels.on_retry, tries, &delay);
+                    metrics.retry();
+                    open_telemetry::retrying::<Out>(tries, &delay);
+                    let waiting_since = clock.now();
+                    match options.delay_anchor {
+                        DelayAnchor::AttemptEnd => options.delay_executor.delay_by(delay),
+                        DelayAnchor::AttemptStart => {
+                            options.delay_executor.delay_since(delay, started_at, clock)
+                        }
+                    }
+                    if options.delay_executor.is_cancelled() {
+                        return Err(cancelled(tries, Some(out), log_levels, metrics, clock));
+                    }
+                    last_wait = Some(clock.elapsed_since(waiting_since));
                     tries += 1;
                 }
                 None => {
-                    tracing::error!(tries, last_output = ?out, "Operation was not successful after maximum retries. Aborting with last output seen.");
-                    return out;
+                    logging::giving_up(log_levels.on_give_up, tries, &out);
+                    logging::record_attempts(tries);
+                    metrics.give_up();
+                    open_telemetry::giving_up::<Out>(tries);
+                    metrics.finish(tries, clock);
+                    return Ok(out);
                 }
             },
         };
@@ -186,90 +476,440 @@ where
 }
 
 #[cfg(feature = "async")]
-#[tracing::instrument(level = "debug", name = "retry_async", skip(operation))]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", name = "retry_async", skip(operation))
+)]
 pub fn retry_async<Out, Op>(operation: Op) -> AsyncNeedsDelayStrategy<Out, Op>
 where
     Out: NeedsRetry + Debug,
     Op: AsyncFn() -> Out,
 {
-    AsyncNeedsDelayStrategy { operation }
+    AsyncNeedsDelayStrategy {
+        operation,
+        delay_executor: DefaultAsyncSleep,
+        operation_name: None,
+        log_levels: None,
+    }
 }
 
 #[cfg(feature = "async")]
-pub struct AsyncNeedsDelayStrategy<Out, Op>
+pub struct AsyncNeedsDelayStrategy<Out, Op, DelayExec = DefaultAsyncSleep>
 where
     Out: NeedsRetry + Debug,
     Op: AsyncFn() -> Out,
 {
     operation: Op,
+    delay_executor: DelayExec,
+    operation_name: Option<Cow<'static, str>>,
+    log_levels: Option<LogLevels>,
 }
 
 #[cfg(feature = "async")]
-impl<Out, Op> AsyncNeedsDelayStrategy<Out, Op>
+impl<Out, Op, DelayExec> AsyncNeedsDelayStrategy<Out, Op, DelayExec>
 where
     Out: NeedsRetry + Debug,
     Op: AsyncFn() -> Out,
 {
-    pub async fn delayed_by<DelayStrat>(self, delay: DelayStrat) -> Out
+    /// Names the retried operation. The name is used to label the recorded metrics (feature `metrics`).
+    pub fn named(mut self, operation_name: impl Into<Cow<'static, str>>) -> Self {
+        self.operation_name = Some(operation_name.into());
+        self
+    }
+
+    /// Overwrites the globally configured log levels (see `set_default_log_levels`) for this retry loop.
+    pub fn with_log_levels(mut self, log_levels: LogLevels) -> Self {
+        self.log_levels = Some(log_levels);
+        self
+    }
+
+    /// Sleeps through `delay_executor` instead of the `DefaultAsyncSleep` executor between attempts.
+    ///
+    /// Required when no runtime feature is enabled.
+    pub fn with_executor<E>(self, delay_executor: E) -> AsyncNeedsDelayStrategy<Out, Op, E>
     where
-        DelayStrat: DelayStrategy<StdDuration>,
+        E: AsyncDelayExecutor<StdDuration>,
     {
-        retry_async_with_options(
-            self.operation,
-            RetryAsyncOptions {
-                delay_strategy: delay,
-                delay_executor: TokioSleep,
-                _marker: PhantomData,
-            },
-        )
-        .await
+        AsyncNeedsDelayStrategy {
+            operation: self.operation,
+            delay_executor,
+            operation_name: self.operation_name,
+            log_levels: self.log_levels,
+        }
+    }
+
+    /// Runs the retry loop, sleeping through the configured executor (`DefaultAsyncSleep`, unless changed through
+    /// `with_executor`) between attempts.
+    ///
+    /// Strategies may yield any delay type implementing `ToStdDuration`.
+    pub async fn delayed_by<Delay, DelayStrat>(self, delay: DelayStrat) -> Out
+    where
+        Delay: ToStdDuration + Debug + Clone,
+        DelayStrat: DelayStrategy<Delay>,
+        DelayExec: AsyncDelayExecutor<StdDuration>,
+    {
+        let mut options = RetryAsyncOptions::builder()
+            .strategy(delay)
+            .executor(ConvertDelay(self.delay_executor))
+            .build();
+        options.operation_name = self.operation_name;
+        options.log_levels = self.log_levels;
+        retry_async_with_options(self.operation, options).await
+    }
+}
+
+/// Like `retry_async`, but the retry loop is guaranteed to be `Send`, so it can be spawned onto a multi-threaded
+/// runtime, e.g. through `tokio::spawn(retry_async_send(..).delayed_by(..))`.
+///
+/// As `AsyncFn` closures cannot (yet) promise to return `Send` futures, the operation must be a function returning a
+/// `Send` future, like `|| async { .. }` or `|| client.fetch()`.
+#[cfg(feature = "async")]
+#[must_use = "Call `delayed_by` on the returned value to complete the retry strategy configuration."]
+pub fn retry_async_send<Out, Op, Fut>(operation: Op) -> SendAsyncNeedsDelayStrategy<Op>
+where
+    Out: NeedsRetry + Debug + Send,
+    Op: Fn() -> Fut + Send + Sync,
+    Fut: Future<Output = Out> + Send,
+{
+    SendAsyncNeedsDelayStrategy {
+        operation,
+        delay_executor: DefaultAsyncSleep,
+        operation_name: None,
+        log_levels: None,
     }
 }
 
 #[cfg(feature = "async")]
-#[derive(Debug)]
-pub struct RetryAsyncOptions<
+pub struct SendAsyncNeedsDelayStrategy<Op, DelayExec = DefaultAsyncSleep> {
+    operation: Op,
+    delay_executor: DelayExec,
+    operation_name: Option<Cow<'static, str>>,
+    log_levels: Option<LogLevels>,
+}
+
+#[cfg(feature = "async")]
+impl<Out, Op, Fut, DelayExec> SendAsyncNeedsDelayStrategy<Op, DelayExec>
+where
+    Out: NeedsRetry + Debug + Send,
+    Op: Fn() -> Fut + Send + Sync,
+    Fut: Future<Output = Out> + Send,
+{
+    /// Names the retried operation. The name is used to label the recorded metrics (feature `metrics`).
+    pub fn named(mut self, operation_name: impl Into<Cow<'static, str>>) -> Self {
+        self.operation_name = Some(operation_name.into());
+        self
+    }
+
+    /// Overwrites the globally configured log levels (see `set_default_log_levels`) for this retry loop.
+    pub fn with_log_levels(mut self, log_levels: LogLevels) -> Self {
+        self.log_levels = Some(log_levels);
+        self
+    }
+
+    /// Sleeps through `delay_executor` instead of the `DefaultAsyncSleep` executor between attempts.
+    ///
+    /// Required when no runtime feature is enabled.
+    pub fn with_executor<E>(self, delay_executor: E) -> SendAsyncNeedsDelayStrategy<Op, E>
+    where
+        E: SendAsyncDelayExecutor<StdDuration>,
+    {
+        SendAsyncNeedsDelayStrategy {
+            operation: self.operation,
+            delay_executor,
+            operation_name: self.operation_name,
+            log_levels: self.log_levels,
+        }
+    }
+
+    /// Runs the retry loop, sleeping through the configured executor (`DefaultAsyncSleep`, unless changed through
+    /// `with_executor`) between attempts.
+    ///
+    /// Strategies may yield any delay type implementing `ToStdDuration`.
+    pub fn delayed_by<Delay, DelayStrat>(
+        self,
+        delay: DelayStrat,
+    ) -> impl Future<Output = Out> + Send
+    where
+        Delay: ToStdDuration + Debug + Clone + Send + Sync,
+        DelayStrat: DelayStrategy<Delay> + Send,
+        DelayExec: SendAsyncDelayExecutor<StdDuration>,
+    {
+        let mut options = RetryAsyncOptions::builder()
+            .strategy(delay)
+            .executor(ConvertDelay(self.delay_executor))
+            .build();
+        options.operation_name = self.operation_name;
+        options.log_levels = self.log_levels;
+        retry_async_send_with_options(self.operation, options)
+    }
+}
+
+#[cfg(feature = "async")]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        name = "retry_async_with_delay_strategy",
+        skip(operation),
+        fields(attempts = tracing::field::Empty)
+    )
+)]
+pub async fn retry_async_with_options<Delay, DelayStrat, DelayExec, Out>(
+    operation: impl AsyncFn() -> Out,
+    options: RetryAsyncOptions<Delay, DelayStrat, DelayExec>,
+) -> Out
+where
     Delay: Debug + Clone,
     DelayStrat: DelayStrategy<Delay>,
     DelayExec: AsyncDelayExecutor<Delay>,
-> {
-    pub delay_strategy: DelayStrat,
-    pub delay_executor: DelayExec,
-    pub _marker: PhantomData<Delay>,
+    Out: NeedsRetry + Debug,
+{
+    let operation = &operation;
+    let delay_executor = &options.delay_executor;
+    let clock = &*options.clock;
+    retry_async_loop(
+        move || operation(),
+        move |delay, since| async move {
+            match since {
+                Some(since) => delay_executor.delay_since(delay, since, clock).await,
+                None => delay_executor.delay_by(delay).await,
+            }
+        },
+        options.delay_strategy,
+        options.delay_anchor,
+        options.operation_name.as_ref(),
+        options.log_levels,
+        clock,
+        CancelSignal::on(pin!(std::future::pending())),
+    )
+    .await
+    .unwrap_or_else(|_| unreachable!("never cancelled"))
 }
 
+/// Like `retry_async_with_options`, but stops retrying as soon as `cancel` completes, e.g. during a graceful shutdown.
+///
+/// A pending delay is interrupted immediately. A running attempt is only interrupted when configured through
+/// `CancelSignal::interrupting_attempts`, otherwise it is awaited first. A cancelled retry loop returns `Cancelled`,
+/// carrying the output of the last completed attempt. A retry loop finishing on its own returns its output as `Ok`,
+/// even when it gave up.
 #[cfg(feature = "async")]
-#[tracing::instrument(
-    level = "debug",
-    name = "retry_async_with_delay_strategy",
-    skip(operation)
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        name = "retry_async_with_cancellation",
+        skip(operation, cancel),
+        fields(attempts = tracing::field::Empty)
+    )
 )]
-pub async fn retry_async_with_options<Delay, DelayStrat, DelayExec, Out>(
+pub async fn retry_async_with_cancellation<Delay, DelayStrat, DelayExec, Out, Signal>(
     operation: impl AsyncFn() -> Out,
-    mut options: RetryAsyncOptions<Delay, DelayStrat, DelayExec>,
-) -> Out
+    options: RetryAsyncOptions<Delay, DelayStrat, DelayExec>,
+    cancel: CancelSignal<Signal>,
+) -> Result<Out, Cancelled<Out>>
 where
     Delay: Debug + Clone,
     DelayStrat: DelayStrategy<Delay>,
     DelayExec: AsyncDelayExecutor<Delay>,
     Out: NeedsRetry + Debug,
+    Signal: Future<Output = ()>,
+{
+    let operation = &operation;
+    let delay_executor = &options.delay_executor;
+    let clock = &*options.clock;
+    retry_async_loop(
+        move || operation(),
+        move |delay, since| async move {
+            match since {
+                Some(since) => delay_executor.delay_since(delay, since, clock).await,
+                None => delay_executor.delay_by(delay).await,
+            }
+        },
+        options.delay_strategy,
+        options.delay_anchor,
+        options.operation_name.as_ref(),
+        options.log_levels,
+        clock,
+        CancelSignal {
+            signal: pin!(cancel.signal),
+            interrupt_attempts: cancel.interrupt_attempts,
+        },
+    )
+    .await
+}
+
+/// Like `retry_async_with_options`, but guarantees the returned future to be `Send`, so it can be spawned onto a
+/// multi-threaded runtime (e.g. through `tokio::spawn`), even from generic code.
+///
+/// This requires the operation to return `Send` futures and the executor to implement `SendAsyncDelayExecutor`.
+#[cfg(feature = "async")]
+pub fn retry_async_send_with_options<Delay, DelayStrat, DelayExec, Out, Op, Fut>(
+    operation: Op,
+    options: RetryAsyncOptions<Delay, DelayStrat, DelayExec>,
+) -> impl Future<Output = Out> + Send
+where
+    Delay: Debug + Clone + Send + Sync,
+    DelayStrat: DelayStrategy<Delay> + Send,
+    DelayExec: SendAsyncDelayExecutor<Delay>,
+    Out: NeedsRetry + Debug + Send,
+    Op: Fn() -> Fut + Send + Sync,
+    Fut: Future<Output = Out> + Send,
+{
+    retry_async_send_instrumented(operation, options)
+}
+
+#[cfg(feature = "async")]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        name = "retry_async_send_with_options",
+        skip(operation),
+        fields(attempts = tracing::field::Empty)
+    )
+)]
+async fn retry_async_send_instrumented<Delay, DelayStrat, DelayExec, Out, Op, Fut>(
+    operation: Op,
+    options: RetryAsyncOptions<Delay, DelayStrat, DelayExec>,
+) -> Out
+where
+    Delay: Debug + Clone + Send + Sync,
+    DelayStrat: DelayStrategy<Delay> + Send,
+    DelayExec: SendAsyncDelayExecutor<Delay>,
+    Out: NeedsRetry + Debug + Send,
+    Op: Fn() -> Fut + Send + Sync,
+    Fut: Future<Output = Out> + Send,
+{
+    let delay_executor = &options.delay_executor;
+    let clock = &*options.clock;
+    retry_async_loop(
+        &operation,
+        move |delay, since| async move {
+            match since {
+                Some(since) => {
+                    SendAsyncDelayExecutor::delay_since(delay_executor, delay, since, clock).await
+                }
+                None => SendAsyncDelayExecutor::delay_by(delay_executor, delay).await,
+            }
+        },
+        options.delay_strategy,
+        options.delay_anchor,
+        options.operation_name.as_ref(),
+        options.log_levels,
+        clock,
+        CancelSignal::on(pin!(std::future::pending())),
+    )
+    .await
+    .unwrap_or_else(|_| unreachable!("never cancelled"))
+}
+
+/// The retry loop shared by all asynchronous entry points.
+///
+/// Entry points without cancellation support pass a signal which never completes. `delay_by` receives the start of
+/// the attempt when delays are anchored there (see `DelayAnchor`).
+#[cfg(feature = "async")]
+#[allow(clippy::too_many_arguments)]
+async fn retry_async_loop<Delay, Out, OpFut, DelayFut, Signal>(
+    operation: impl Fn() -> OpFut,
+    delay_by: impl Fn(Delay, Option<Instant>) -> DelayFut,
+    mut delay_strategy: impl DelayStrategy<Delay>,
+    delay_anchor: DelayAnchor,
+    operation_name: Option<&Cow<'static, str>>,
+    log_levels: Option<LogLevels>,
+    clock: &dyn Clock,
+    mut cancel: CancelSignal<Pin<&mut Signal>>,
+) -> Result<Out, Cancelled<Out>>
+where
+    Delay: Debug + Clone,
+    Out: NeedsRetry + Debug,
+    OpFut: Future<Output = Out>,
+    DelayFut: Future<Output = ()>,
+    Signal: Future<Output = ()>,
 {
+    let log_levels = log_levels.unwrap_or_else(default_log_levels);
+    let metrics = RetryMetrics::start(operation_name, clock);
+    let max_attempts = overrides::max_attempts();
     let mut tries: usize = 1;
+    let mut last_wait: Option<StdDuration> = None;
+    let mut last_output: Option<Out> = None;
     loop {
-        let out = operation().await;
-        match out.needs_retry() {
-            false => return out,
-            true => match options.delay_strategy.next_delay() {
+        let started_at = clock.now();
+        let attempt = logging::Attempt::start(tries, last_wait, clock);
+        metrics.attempt();
+        let out = match cancel.interrupt_attempts {
+            true => {
+                let run = attempt.run_async(operation());
+                match cancellation::until_cancelled(run, cancel.signal.as_mut()).await {
+                    Some(out) => out,
+                    None => return Err(cancelled(tries, last_output, log_levels, metrics, clock)),
+                }
+            }
+            false => attempt.run_async(operation()).await,
+        };
+        let needs_retry = out.needs_retry();
+        attempt.finish(needs_retry, clock);
+        match needs_retry {
+            false => {
+                logging::record_attempts(tries);
+                metrics.finish(tries, clock);
+                return Ok(out);
+            }
+            true => match next_delay(&mut delay_strategy, tries, max_attempts) {
                 Some(delay) => {
-                    tracing::debug!(tries, delay = ?delay, "Operation was not successful. Waiting...");
-                    options.delay_executor.delay_by(delay.clone()).await;
+                    logging::retrying(log_levels.on_retry, tries, &delay);
+                    metrics.retry();
+                    open_telemetry::retrying::<Out>(tries, &delay);
+                    last_output = Some(out);
+                    let since = (delay_anchor == DelayAnchor::AttemptStart).then_some(started_at);
+                    let waiting_since = clock.now();
+                    let delayed = delay_by(delay, since);
+                    if cancellation::until_cancelled(delayed, cancel.signal.as_mut())
+                        .await
+                        .is_none()
+                    {
+                        return Err(cancelled(tries, last_output, log_levels, metrics, clock));
+                    }
+                    last_wait = Some(clock.elapsed_since(waiting_since));
                     tries += 1;
                 }
                 None => {
-                    tracing::error!(tries, last_output = ?out, "Operation was not successful after maximum retries. Aborting with last output seen.");
-                    return out;
+                    logging::giving_up(log_levels.on_give_up, tries, &out);
+                    logging::record_attempts(tries);
+                    metrics.give_up();
+                    open_telemetry::giving_up::<Out>(tries);
+                    metrics.finish(tries, clock);
+                    return Ok(out);
                 }
             },
         };
     }
 }
+
+/// Reports the cancellation of a retry loop after `tries` attempts.
+fn cancelled<Out: Debug>(
+    tries: usize,
+    last_output: Option<Out>,
+    log_levels: LogLevels,
+    metrics: RetryMetrics,
+    clock: &dyn Clock,
+) -> Cancelled<Out> {
+    logging::cancelled(log_levels.on_cancel, tries, &last_output);
+    logging::record_attempts(tries);
+    metrics.finish(tries, clock);
+    Cancelled {
+        attempts: tries,
+        last_output,
+    }
+}
+
+/// Asks the strategy for the next delay, unless the `overrides::max_attempts` limit is already reached.
+fn next_delay<Delay>(
+    strategy: &mut impl DelayStrategy<Delay>,
+    tries: usize,
+    max_attempts: Option<usize>,
+) -> Option<Delay> {
+    match max_attempts {
+        Some(max_attempts) if tries >= max_attempts => None,
+        _ => strategy.next_delay(),
+    }
+}
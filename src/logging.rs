@@ -0,0 +1,333 @@
+use crate::StdDuration;
+use crate::clock::Clock;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Severity with which an event of the retry loop is reported.
+///
+/// Events are emitted through `tracing` (feature `tracing`, enabled by default) and/or `log` (feature `log`).
+/// If both features are enabled, every event is emitted through both backends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum LogLevel {
+    /// The event is not reported at all.
+    Off,
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    const fn to_u8(self) -> u8 {
+        match self {
+            LogLevel::Off => 0,
+            LogLevel::Trace => 1,
+            LogLevel::Debug => 2,
+            LogLevel::Info => 3,
+            LogLevel::Warn => 4,
+            LogLevel::Error => 5,
+        }
+    }
+
+    const fn from_u8(value: u8) -> Self {
+        match value {
+            0 => LogLevel::Off,
+            1 => LogLevel::Trace,
+            2 => LogLevel::Debug,
+            3 => LogLevel::Info,
+            4 => LogLevel::Warn,
+            _ => LogLevel::Error,
+        }
+    }
+}
+
+/// Levels used when reporting the events of a retry loop.
+///
+/// Created through `LogLevels::new` (or `LogLevels::DEFAULT`), as further events may be added in the future.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct LogLevels {
+    /// Level of the event emitted when an attempt failed and the operation is about to be retried.
+    pub on_retry: LogLevel,
+
+    /// Level of the event emitted when an attempt failed and no further retries are allowed.
+    pub on_give_up: LogLevel,
+
+    /// Level of the event emitted when the retry loop was cancelled. Defaults to `Info` when omitted in configuration.
+    #[cfg_attr(feature = "serde", serde(default = "default_on_cancel"))]
+    pub on_cancel: LogLevel,
+}
+
+impl LogLevels {
+    /// The levels used unless configured otherwise: `Debug` for retries, `Error` for give-ups and `Info` for
+    /// cancellations.
+    pub const DEFAULT: LogLevels = LogLevels {
+        on_retry: LogLevel::Debug,
+        on_give_up: LogLevel::Error,
+        on_cancel: LogLevel::Info,
+    };
+
+    /// Reports retries at `on_retry` and give-ups at `on_give_up`, cancellations at the default `Info` level.
+    pub const fn new(on_retry: LogLevel, on_give_up: LogLevel) -> Self {
+        Self {
+            on_retry,
+            on_give_up,
+            on_cancel: Self::DEFAULT.on_cancel,
+        }
+    }
+
+    /// Reports cancellations of the retry loop at `on_cancel`.
+    pub const fn with_on_cancel(mut self, on_cancel: LogLevel) -> Self {
+        self.on_cancel = on_cancel;
+        self
+    }
+}
+
+#[cfg(feature = "serde")]
+fn default_on_cancel() -> LogLevel {
+    LogLevels::DEFAULT.on_cancel
+}
+
+impl Default for LogLevels {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+static DEFAULT_ON_RETRY: AtomicU8 = AtomicU8::new(LogLevels::DEFAULT.on_retry.to_u8());
+static DEFAULT_ON_GIVE_UP: AtomicU8 = AtomicU8::new(LogLevels::DEFAULT.on_give_up.to_u8());
+static DEFAULT_ON_CANCEL: AtomicU8 = AtomicU8::new(LogLevels::DEFAULT.on_cancel.to_u8());
+
+/// Sets the log levels used by all retry loops not configured with explicit log levels.
+pub fn set_default_log_levels(levels: LogLevels) {
+    DEFAULT_ON_RETRY.store(levels.on_retry.to_u8(), Ordering::Relaxed);
+    DEFAULT_ON_GIVE_UP.store(levels.on_give_up.to_u8(), Ordering::Relaxed);
+    DEFAULT_ON_CANCEL.store(levels.on_cancel.to_u8(), Ordering::Relaxed);
+}
+
+/// Returns the log levels used by all retry loops not configured with explicit log levels.
+pub fn default_log_levels() -> LogLevels {
+    LogLevels {
+        on_retry: LogLevel::from_u8(DEFAULT_ON_RETRY.load(Ordering::Relaxed)),
+        on_give_up: LogLevel::from_u8(DEFAULT_ON_GIVE_UP.load(Ordering::Relaxed)),
+        on_cancel: LogLevel::from_u8(DEFAULT_ON_CANCEL.load(Ordering::Relaxed)),
+    }
+}
+
+#[cfg(feature = "tracing")]
+macro_rules! tracing_event {
+    ($level:expr, $($args:tt)+) => {
+        match $level {
+            LogLevel::Off => {}
+            LogLevel::Trace => tracing::event!(tracing::Level::TRACE, $($args)+),
+            LogLevel::Debug => tracing::event!(tracing::Level::DEBUG, $($args)+),
+            LogLevel::Info => tracing::event!(tracing::Level::INFO, $($args)+),
+            LogLevel::Warn => tracing::event!(tracing::Level::WARN, $($args)+),
+            LogLevel::Error => tracing::event!(tracing::Level::ERROR, $($args)+),
+        }
+    };
+}
+
+#[cfg(feature = "log")]
+fn to_log_level(level: LogLevel) -> Option<log::Level> {
+    match level {
+        LogLevel::Off => None,
+        LogLevel::Trace => Some(log::Level::Trace),
+        LogLevel::Debug => Some(log::Level::Debug),
+        LogLevel::Info => Some(log::Level::Info),
+        LogLevel::Warn => Some(log::Level::Warn),
+        LogLevel::Error => Some(log::Level::Error),
+    }
+}
+
+#[allow(unused_variables)]
+pub(crate) fn retrying(level: LogLevel, tries: usize, delay: &dyn Debug) {
+    #[cfg(feature = "tracing")]
+    tracing_event!(level, tries, delay = ?delay, "Operation was not successful. Waiting...");
+
+    #[cfg(feature = "log")]
+    if let Some(level) = to_log_level(level) {
+        log::log!(
+            level,
+            "Operation was not successful. Waiting... tries={tries} delay={delay:?}"
+        );
+    }
+}
+
+#[allow(unused_variables)]
+pub(crate) fn giving_up(level: LogLevel, tries: usize, last_output: &dyn Debug) {
+    #[cfg(feature = "tracing")]
+    tracing_event!(level, tries, last_output = ?last_output, "Operation was not successful after maximum retries. Aborting with last output seen.");
+
+    #[cfg(feature = "log")]
+    if let Some(level) = to_log_level(level) {
+        log::log!(
+            level,
+            "Operation was not successful after maximum retries. Aborting with last output seen. tries={tries} last_output={last_output:?}"
+        );
+    }
+}
+
+#[allow(unused_variables)]
+pub(crate) fn cancelled(level: LogLevel, tries: usize, last_output: &dyn Debug) {
+    #[cfg(feature = "tracing")]
+    tracing_event!(level, tries, last_output = ?last_output, "Retry loop was cancelled. Aborting with last output seen.");
+
+    #[cfg(feature = "log")]
+    if let Some(level) = to_log_level(level) {
+        log::log!(
+            level,
+            "Retry loop was cancelled. Aborting with last output seen. tries={tries} last_output={last_output:?}"
+        );
+    }
+}
+
+#[cfg(all(unix, feature = "signals"))]
+#[allow(unused_variables)]
+pub(crate) fn shutdown_requested(signal: i32) {
+    #[cfg(feature = "tracing")]
+    tracing::info!(signal, "Received shutdown signal. Cancelling retry loops.");
+
+    #[cfg(feature = "log")]
+    log::info!("Received shutdown signal. Cancelling retry loops. signal={signal}");
+}
+
+#[allow(unused_variables)]
+pub(crate) fn invalid_environment_variable(name: &str, value: &str, expected: &str) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(
+        name,
+        value,
+        expected,
+        "Ignoring invalid environment variable."
+    );
+
+    #[cfg(feature = "log")]
+    log::warn!(
+        "Ignoring invalid environment variable. name={name} value={value} expected={expected}"
+    );
+}
+
+#[cfg(feature = "registry")]
+#[allow(unused_variables)]
+pub(crate) fn policies_reloaded(path: &std::path::Path) {
+    #[cfg(feature = "tracing")]
+    tracing::info!(path = %path.display(), "Reloaded retry policies.");
+
+    #[cfg(feature = "log")]
+    log::info!("Reloaded retry policies. path={}", path.display());
+}
+
+#[cfg(feature = "registry")]
+#[allow(unused_variables)]
+pub(crate) fn policy_reload_failed(path: &std::path::Path, err: &dyn std::error::Error) {
+    #[cfg(feature = "tracing")]
+    tracing::error!(path = %path.display(), error = %err, "Could not reload retry policies. Keeping previous policies.");
+
+    #[cfg(feature = "log")]
+    log::error!(
+        "Could not reload retry policies. Keeping previous policies. path={} error={err}",
+        path.display()
+    );
+}
+
+/// A single invocation of the retried operation.
+///
+/// When the `tracing` feature is enabled, each attempt is reported as an `attempt` span (child of the span of the retry
+/// loop), carrying the fields `attempt`, `delay_ms` (time waited before this attempt, measured by the clock of the
+/// retry loop), `outcome` and `elapsed_ms`.
+pub(crate) struct Attempt {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg(feature = "tracing")]
+    started_at: std::time::Instant,
+}
+
+impl Attempt {
+    #[allow(unused_variables)]
+    pub(crate) fn start(attempt: usize, waited: Option<StdDuration>, clock: &dyn Clock) -> Self {
+        Self {
+            #[cfg(feature = "tracing")]
+            span: tracing::debug_span!(
+                "attempt",
+                attempt,
+                delay_ms = waited.map(millis),
+                outcome = tracing::field::Empty,
+                elapsed_ms = tracing::field::Empty,
+            ),
+            #[cfg(feature = "tracing")]
+            started_at: clock.now(),
+        }
+    }
+
+    /// Runs the (synchronous) operation inside this attempt's span.
+    pub(crate) fn run<Out>(&self, operation: impl FnOnce() -> Out) -> Out {
+        #[cfg(feature = "tracing")]
+        return self.span.in_scope(operation);
+
+        #[cfg(not(feature = "tracing"))]
+        return operation();
+    }
+
+    /// Runs the (asynchronous) operation inside this attempt's span.
+    #[cfg(feature = "async")]
+    pub(crate) async fn run_async<Out>(&self, operation: impl Future<Output = Out>) -> Out {
+        #[cfg(feature = "tracing")]
+        return tracing::Instrument::instrument(operation, self.span.clone()).await;
+
+        #[cfg(not(feature = "tracing"))]
+        return operation.await;
+    }
+
+    #[allow(unused_variables)]
+    pub(crate) fn finish(self, needs_retry: bool, clock: &dyn Clock) {
+        #[cfg(feature = "tracing")]
+        {
+            let outcome = match needs_retry {
+                true => "failure",
+                false => "success",
+            };
+            self.span.record("outcome", outcome);
+            self.span
+                .record("elapsed_ms", millis(clock.elapsed_since(self.started_at)));
+        }
+    }
+}
+
+/// Whole milliseconds of `duration`, saturating at `u64::MAX`.
+#[cfg(feature = "tracing")]
+fn millis(duration: StdDuration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+/// Records the total number of attempts made on the span of the retry loop.
+#[allow(unused_variables)]
+pub(crate) fn record_attempts(attempts: usize) {
+    #[cfg(feature = "tracing")]
+    tracing::Span::current().record("attempts", attempts);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use assertr::prelude::*;
+
+    #[test]
+    fn default_log_levels_can_be_overwritten_globally() {
+        assert_that(default_log_levels()).is_equal_to(LogLevels::DEFAULT);
+
+        let quiet = LogLevels::new(LogLevel::Off, LogLevel::Warn).with_on_cancel(LogLevel::Debug);
+        set_default_log_levels(quiet);
+        assert_that(default_log_levels()).is_equal_to(quiet);
+
+        set_default_log_levels(LogLevels::DEFAULT);
+        assert_that(default_log_levels()).is_equal_to(LogLevels::DEFAULT);
+    }
+}
@@ -0,0 +1,162 @@
+use crate::NeedsRetry;
+use std::fmt::Debug;
+
+/// Adds a `retry` event to the currently active OpenTelemetry span (feature `opentelemetry`).
+///
+/// Attributes: `retry.attempt` (the failed attempt), `retry.delay` (the delay until the next attempt) and `error.type`
+/// (see `NeedsRetry::failure_type`, e.g. the error type of a `Result`).
+#[allow(unused_variables)]
+pub(crate) fn retrying<Out: NeedsRetry>(attempt: usize, delay: &dyn Debug) {
+    #[cfg(feature = "opentelemetry")]
+    opentelemetry::trace::get_active_span(|span| {
+        span.add_event(
+            "retry",
+            vec![
+                opentelemetry::KeyValue::new("retry.attempt", attempt_number(attempt)),
+                opentelemetry::KeyValue::new("retry.delay", format!("{delay:?}")),
+                opentelemetry::KeyValue::new("error.type", Out::failure_type()),
+            ],
+        );
+    });
+}
+
+/// Adds a `retry.give_up` event to the currently active OpenTelemetry span and sets its status to `Error`
+/// (feature `opentelemetry`).
+#[allow(unused_variables)]
+pub(crate) fn giving_up<Out: NeedsRetry>(attempts: usize) {
+    #[cfg(feature = "opentelemetry")]
+    opentelemetry::trace::get_active_span(|span| {
+        span.add_event(
+            "retry.give_up",
+            vec![
+                opentelemetry::KeyValue::new("retry.attempts", attempt_number(attempts)),
+                opentelemetry::KeyValue::new("error.type", Out::failure_type()),
+            ],
+        );
+        span.set_status(opentelemetry::trace::Status::error(format!(
+            "Operation was not successful after {attempts} attempts."
+        )));
+    });
+}
+
+#[cfg(feature = "opentelemetry")]
+fn attempt_number(attempt: usize) -> i64 {
+    i64::try_from(attempt).unwrap_or(i64::MAX)
+}
+
+#[cfg(all(test, feature = "opentelemetry"))]
+mod test {
+    use super::*;
+    use assertr::prelude::*;
+    use opentelemetry::KeyValue;
+    use opentelemetry::trace::{Span, SpanContext, Status};
+    use std::borrow::Cow;
+    use std::sync::{Arc, Mutex};
+    use std::time::SystemTime;
+
+    #[derive(Debug, Default)]
+    struct Recorded {
+        events: Vec<(Cow<'static, str>, Vec<KeyValue>)>,
+        status: Option<Status>,
+    }
+
+    #[derive(Debug)]
+    struct RecordingSpan {
+        context: SpanContext,
+        recorded: Arc<Mutex<Recorded>>,
+    }
+
+    impl Span for RecordingSpan {
+        fn add_event_with_timestamp<T>(
+            &mut self,
+            name: T,
+            _timestamp: SystemTime,
+            attributes: Vec<KeyValue>,
+        ) where
+            T: Into<Cow<'static, str>>,
+        {
+            self.recorded
+                .lock()
+                .expect("not poisoned")
+                .events
+                .push((name.into(), attributes));
+        }
+
+        fn span_context(&self) -> &SpanContext {
+            &self.context
+        }
+
+        fn is_recording(&self) -> bool {
+            true
+        }
+
+        fn set_attribute(&mut self, _attribute: KeyValue) {}
+
+        fn set_status(&mut self, status: Status) {
+            self.recorded.lock().expect("not poisoned").status = Some(status);
+        }
+
+        fn update_name<T>(&mut self, _new_name: T)
+        where
+            T: Into<Cow<'static, str>>,
+        {
+        }
+
+        fn add_link(&mut self, _span_context: SpanContext, _attributes: Vec<KeyValue>) {}
+
+        fn end_with_timestamp(&mut self, _timestamp: SystemTime) {}
+    }
+
+    #[test]
+    fn adds_retry_events_and_sets_error_status_on_give_up() {
+        let recorded = Arc::new(Mutex::new(Recorded::default()));
+        let _guard = opentelemetry::trace::mark_span_as_active(RecordingSpan {
+            context: SpanContext::empty_context(),
+            recorded: recorded.clone(),
+        });
+
+        retrying::<Result<(), u8>>(1, &std::time::Duration::from_millis(100));
+        giving_up::<Result<(), u8>>(2);
+
+        let recorded = recorded.lock().expect("not poisoned");
+        assert_that(recorded.events.len()).is_equal_to(2);
+
+        let (name, attributes) = &recorded.events[0];
+        assert_that(name.as_ref()).is_equal_to("retry");
+        assert_that(attributes.as_slice()).contains_exactly([
+            KeyValue::new("retry.attempt", 1),
+            KeyValue::new("retry.delay", "100ms"),
+            KeyValue::new("error.type", "u8"),
+        ]);
+
+        let (name, attributes) = &recorded.events[1];
+        assert_that(name.as_ref()).is_equal_to("retry.give_up");
+        assert_that(attributes.as_slice()).contains_exactly([
+            KeyValue::new("retry.attempts", 2),
+            KeyValue::new("error.type", "u8"),
+        ]);
+
+        assert_that(recorded.status.clone()).is_equal_to(Some(Status::error(
+            "Operation was not successful after 2 attempts.",
+        )));
+    }
+
+    #[test]
+    fn policies_report_the_failure_type_of_the_operation() {
+        let recorded = Arc::new(Mutex::new(Recorded::default()));
+        let _guard = opentelemetry::trace::mark_span_as_active(RecordingSpan {
+            context: SpanContext::empty_context(),
+            recorded: recorded.clone(),
+        });
+
+        let policy = crate::RetryPolicy::new(crate::delay::Schedule::None { retries: 1 });
+        let out = policy.retry(|| -> Result<(), u8> { Err(1) });
+
+        assert_that(out).is_err();
+        let recorded = recorded.lock().expect("not poisoned");
+        assert_that(recorded.events.len()).is_equal_to(2);
+        for (_, attributes) in &recorded.events {
+            assert_that(attributes.contains(&KeyValue::new("error.type", "u8"))).is_true();
+        }
+    }
+}
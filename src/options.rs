@@ -0,0 +1,260 @@
+use crate::LogLevels;
+use crate::clock::{BoxedClock, Clock, SystemClock};
+use crate::delay_executor::{DelayExecutor, ThreadSleep};
+use crate::delay_strategy::DelayStrategy;
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+/// The point in time the delays of a retry loop are measured from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DelayAnchor {
+    /// Delays start once an attempt finished, so the time between the starts of two attempts grows by the runtime of
+    /// the first one.
+    #[default]
+    AttemptEnd,
+
+    /// Delays start together with the attempt, so "retry every 5s" starts an attempt every 5s, regardless of how long
+    /// each attempt took. An attempt outlasting its delay is retried immediately.
+    ///
+    /// Executors sleep until a deadline derived from the configured `Clock`, which must therefore share their time
+    /// source, e.g. the `TokioClock` when pausing tokio's time. Executors without a notion of deadlines (see
+    /// `DelayExecutor::delay_since`) sleep for the full delay instead.
+    AttemptStart,
+}
+
+/// Options of `retry_with_options`.
+///
+/// Created through `RetryOptions::builder()`. The struct is `#[non_exhaustive]`, so new options can be added without
+/// breaking existing code.
+///
+/// ```rust
+/// use try_again::{IntoStdDuration, RetryOptions, delay, retry_with_options};
+///
+/// let options = RetryOptions::builder()
+///     .strategy(delay::Fixed::of(1.millis()).take(3))
+///     .operation_name("db")
+///     .build();
+///
+/// let outcome = retry_with_options(|| -> Result<(), ()> { Ok(()) }, options);
+/// assert_eq!(outcome, Ok(()));
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct RetryOptions<Delay, DelayStrat, DelayExec> {
+    pub delay_strategy: DelayStrat,
+    pub delay_executor: DelayExec,
+    /// Name of the retried operation, used to label the recorded metrics (feature `metrics`).
+    pub operation_name: Option<Cow<'static, str>>,
+    /// Log levels used by this retry loop. Falls back to `default_log_levels()` when `None`.
+    pub log_levels: Option<LogLevels>,
+    /// Source of the current time, used to measure elapsed time. Defaults to the `SystemClock`.
+    pub clock: BoxedClock,
+    /// The point in time delays are measured from. Defaults to `DelayAnchor::AttemptEnd`.
+    pub delay_anchor: DelayAnchor,
+    _marker: PhantomData<Delay>,
+}
+
+impl RetryOptions<(), (), ()> {
+    /// Starts building options. A delay strategy must be set, the executor defaults to `ThreadSleep`.
+    pub fn builder() -> RetryOptionsBuilder<(), ThreadSleep> {
+        RetryOptionsBuilder {
+            delay_strategy: (),
+            delay_executor: ThreadSleep,
+            operation_name: None,
+            log_levels: None,
+            clock: Box::new(SystemClock),
+            delay_anchor: DelayAnchor::default(),
+        }
+    }
+}
+
+/// Builder of `RetryOptions`, created through `RetryOptions::builder()`.
+#[derive(Debug)]
+#[must_use = "Call `build` to create the options."]
+pub struct RetryOptionsBuilder<DelayStrat, DelayExec> {
+    delay_strategy: DelayStrat,
+    delay_executor: DelayExec,
+    operation_name: Option<Cow<'static, str>>,
+    log_levels: Option<LogLevels>,
+    clock: BoxedClock,
+    delay_anchor: DelayAnchor,
+}
+
+impl<DelayStrat, DelayExec> RetryOptionsBuilder<DelayStrat, DelayExec> {
+    pub fn strategy<S>(self, delay_strategy: S) -> RetryOptionsBuilder<S, DelayExec> {
+        RetryOptionsBuilder {
+            delay_strategy,
+            delay_executor: self.delay_executor,
+            operation_name: self.operation_name,
+            log_levels: self.log_levels,
+            clock: self.clock,
+            delay_anchor: self.delay_anchor,
+        }
+    }
+
+    pub fn executor<E>(self, delay_executor: E) -> RetryOptionsBuilder<DelayStrat, E> {
+        RetryOptionsBuilder {
+            delay_strategy: self.delay_strategy,
+            delay_executor,
+            operation_name: self.operation_name,
+            log_levels: self.log_levels,
+            clock: self.clock,
+            delay_anchor: self.delay_anchor,
+        }
+    }
+
+    /// Names the retried operation. The name is used to label the recorded metrics (feature `metrics`).
+    pub fn operation_name(mut self, operation_name: impl Into<Cow<'static, str>>) -> Self {
+        self.operation_name = Some(operation_name.into());
+        self
+    }
+
+    /// Overwrites the globally configured log levels (see `set_default_log_levels`) for this retry loop.
+    pub fn log_levels(mut self, log_levels: LogLevels) -> Self {
+        self.log_levels = Some(log_levels);
+        self
+    }
+
+    /// Reads the current time from `clock` instead of the `SystemClock`, e.g. a `ManualClock` in tests.
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    /// Measures delays from `delay_anchor`, e.g. `DelayAnchor::AttemptStart` to start attempts on a fixed schedule.
+    pub fn delay_anchor(mut self, delay_anchor: DelayAnchor) -> Self {
+        self.delay_anchor = delay_anchor;
+        self
+    }
+
+    pub fn build<Delay>(self) -> RetryOptions<Delay, DelayStrat, DelayExec>
+    where
+        DelayStrat: DelayStrategy<Delay>,
+        DelayExec: DelayExecutor<Delay>,
+    {
+        RetryOptions {
+            delay_strategy: self.delay_strategy,
+            delay_executor: self.delay_executor,
+            operation_name: self.operation_name,
+            log_levels: self.log_levels,
+            clock: self.clock,
+            delay_anchor: self.delay_anchor,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Options of `retry_async_with_options` and `retry_async_send_with_options`.
+///
+/// Created through `RetryAsyncOptions::builder()`. The struct is `#[non_exhaustive]`, so new options can be added
+/// without breaking existing code.
+#[cfg(feature = "async")]
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct RetryAsyncOptions<Delay, DelayStrat, DelayExec> {
+    pub delay_strategy: DelayStrat,
+    pub delay_executor: DelayExec,
+    /// Name of the retried operation, used to label the recorded metrics (feature `metrics`).
+    pub operation_name: Option<Cow<'static, str>>,
+    /// Log levels used by this retry loop. Falls back to `default_log_levels()` when `None`.
+    pub log_levels: Option<LogLevels>,
+    /// Source of the current time, used to measure elapsed time. Defaults to the `SystemClock`.
+    pub clock: BoxedClock,
+    /// The point in time delays are measured from. Defaults to `DelayAnchor::AttemptEnd`.
+    pub delay_anchor: DelayAnchor,
+    _marker: PhantomData<Delay>,
+}
+
+#[cfg(feature = "async")]
+impl RetryAsyncOptions<(), (), ()> {
+    /// Starts building options. Both a delay strategy and an executor must be set.
+    pub fn builder() -> RetryAsyncOptionsBuilder<(), ()> {
+        RetryAsyncOptionsBuilder {
+            delay_strategy: (),
+            delay_executor: (),
+            operation_name: None,
+            log_levels: None,
+            clock: Box::new(SystemClock),
+            delay_anchor: DelayAnchor::default(),
+        }
+    }
+}
+
+/// Builder of `RetryAsyncOptions`, created through `RetryAsyncOptions::builder()`.
+#[cfg(feature = "async")]
+#[derive(Debug)]
+#[must_use = "Call `build` to create the options."]
+pub struct RetryAsyncOptionsBuilder<DelayStrat, DelayExec> {
+    delay_strategy: DelayStrat,
+    delay_executor: DelayExec,
+    operation_name: Option<Cow<'static, str>>,
+    log_levels: Option<LogLevels>,
+    clock: BoxedClock,
+    delay_anchor: DelayAnchor,
+}
+
+#[cfg(feature = "async")]
+impl<DelayStrat, DelayExec> RetryAsyncOptionsBuilder<DelayStrat, DelayExec> {
+    pub fn strategy<S>(self, delay_strategy: S) -> RetryAsyncOptionsBuilder<S, DelayExec> {
+        RetryAsyncOptionsBuilder {
+            delay_strategy,
+            delay_executor: self.delay_executor,
+            operation_name: self.operation_name,
+            log_levels: self.log_levels,
+            clock: self.clock,
+            delay_anchor: self.delay_anchor,
+        }
+    }
+
+    pub fn executor<E>(self, delay_executor: E) -> RetryAsyncOptionsBuilder<DelayStrat, E> {
+        RetryAsyncOptionsBuilder {
+            delay_strategy: self.delay_strategy,
+            delay_executor,
+            operation_name: self.operation_name,
+            log_levels: self.log_levels,
+            clock: self.clock,
+            delay_anchor: self.delay_anchor,
+        }
+    }
+
+    /// Names the retried operation. The name is used to label the recorded metrics (feature `metrics`).
+    pub fn operation_name(mut self, operation_name: impl Into<Cow<'static, str>>) -> Self {
+        self.operation_name = Some(operation_name.into());
+        self
+    }
+
+    /// Overwrites the globally configured log levels (see `set_default_log_levels`) for this retry loop.
+    pub fn log_levels(mut self, log_levels: LogLevels) -> Self {
+        self.log_levels = Some(log_levels);
+        self
+    }
+
+    /// Reads the current time from `clock` instead of the `SystemClock`, e.g. a `ManualClock` in tests.
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    /// Measures delays from `delay_anchor`, e.g. `DelayAnchor::AttemptStart` to start attempts on a fixed schedule.
+    pub fn delay_anchor(mut self, delay_anchor: DelayAnchor) -> Self {
+        self.delay_anchor = delay_anchor;
+        self
+    }
+
+    /// Creates the options. The executor may implement `AsyncDelayExecutor` (see `retry_async_with_options`) or
+    /// `SendAsyncDelayExecutor` (see `retry_async_send_with_options`).
+    pub fn build<Delay>(self) -> RetryAsyncOptions<Delay, DelayStrat, DelayExec>
+    where
+        DelayStrat: DelayStrategy<Delay>,
+    {
+        RetryAsyncOptions {
+            delay_strategy: self.delay_strategy,
+            delay_executor: self.delay_executor,
+            operation_name: self.operation_name,
+            log_levels: self.log_levels,
+            clock: self.clock,
+            delay_anchor: self.delay_anchor,
+            _marker: PhantomData,
+        }
+    }
+}
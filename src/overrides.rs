@@ -0,0 +1,128 @@
+//! Process-wide overrides of the configured retry behavior, primarily meant to speed up tests.
+//!
+//! - The *time scale* multiplies every delay the provided executors sleep for. A scale of `0.01` turns a 5s backoff
+//!   into 50ms. Custom executors honor it by sleeping for `scaled(delay)`, which keeps retry loops free of any bound
+//!   on the delay type.
+//! - The *max attempts* limit caps the number of attempts of every retry loop, regardless of its delay strategy.
+//!   It can only shorten retry loops, never extend them.
+//!
+//! Both are read from the environment variables `TRY_AGAIN_TIME_SCALE` and `TRY_AGAIN_MAX_ATTEMPTS` on first use, and
+//! can be set programmatically, which takes precedence over the environment.
+
+use crate::StdDuration;
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+/// Environment variable holding the initial time scale, e.g. `TRY_AGAIN_TIME_SCALE=0.01`.
+pub const TIME_SCALE_ENV: &str = "TRY_AGAIN_TIME_SCALE";
+
+/// Environment variable holding the initial max attempts limit, e.g. `TRY_AGAIN_MAX_ATTEMPTS=2`.
+pub const MAX_ATTEMPTS_ENV: &str = "TRY_AGAIN_MAX_ATTEMPTS";
+
+/// Stores the bits of the `f64` time scale.
+static TIME_SCALE: LazyLock<AtomicU64> = LazyLock::new(|| {
+    let scale = read_env(TIME_SCALE_ENV, parse_time_scale, "a non-negative number").unwrap_or(1.0);
+    AtomicU64::new(scale.to_bits())
+});
+
+/// Stores the max attempts limit, `0` meaning "no limit".
+static MAX_ATTEMPTS: LazyLock<AtomicUsize> = LazyLock::new(|| {
+    let max_attempts = read_env(MAX_ATTEMPTS_ENV, parse_max_attempts, "a positive integer");
+    AtomicUsize::new(max_attempts.unwrap_or(0))
+});
+
+/// Sets the factor all delays are multiplied with before sleeping.
+///
+/// # Panics
+///
+/// If `scale` is negative, infinite or NaN.
+pub fn set_time_scale(scale: f64) {
+    assert!(
+        scale.is_finite() && scale >= 0.0,
+        "Time scale must be a finite, non-negative number, got {scale}."
+    );
+    TIME_SCALE.store(scale.to_bits(), Ordering::Relaxed);
+}
+
+/// The factor all delays are multiplied with before sleeping. Defaults to `1.0`.
+pub fn time_scale() -> f64 {
+    f64::from_bits(TIME_SCALE.load(Ordering::Relaxed))
+}
+
+/// Applies the current time scale to `delay`, saturating at `StdDuration::MAX`.
+pub fn scaled(delay: StdDuration) -> StdDuration {
+    match time_scale() {
+        1.0 => delay,
+        scale => {
+            StdDuration::try_from_secs_f64(delay.as_secs_f64() * scale).unwrap_or(StdDuration::MAX)
+        }
+    }
+}
+
+/// Limits every retry loop to at most `max_attempts` attempts (including the first one). `None` removes the limit.
+///
+/// # Panics
+///
+/// If `max_attempts` is `Some(0)`, as every retry loop performs at least one attempt.
+pub fn set_max_attempts(max_attempts: Option<usize>) {
+    assert!(
+        max_attempts != Some(0),
+        "Max attempts must be at least 1, as every retry loop performs at least one attempt."
+    );
+    MAX_ATTEMPTS.store(max_attempts.unwrap_or(0), Ordering::Relaxed);
+}
+
+/// The limit of attempts every retry loop is subject to, if any.
+pub fn max_attempts() -> Option<usize> {
+    match MAX_ATTEMPTS.load(Ordering::Relaxed) {
+        0 => None,
+        max_attempts => Some(max_attempts),
+    }
+}
+
+fn read_env<T>(name: &str, parse: fn(&str) -> Option<T>, expected: &str) -> Option<T> {
+    let value = std::env::var(name).ok()?;
+    let parsed = parse(&value);
+    if parsed.is_none() {
+        crate::logging::invalid_environment_variable(name, &value, expected);
+    }
+    parsed
+}
+
+fn parse_time_scale(value: &str) -> Option<f64> {
+    value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|scale| scale.is_finite() && *scale >= 0.0)
+}
+
+fn parse_max_attempts(value: &str) -> Option<usize> {
+    value
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .filter(|max_attempts| *max_attempts > 0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use assertr::prelude::*;
+
+    #[test]
+    fn parses_valid_time_scales_only() {
+        assert_that(parse_time_scale("0.01")).is_equal_to(Some(0.01));
+        assert_that(parse_time_scale(" 2 ")).is_equal_to(Some(2.0));
+        assert_that(parse_time_scale("-1")).is_none();
+        assert_that(parse_time_scale("NaN")).is_none();
+        assert_that(parse_time_scale("fast")).is_none();
+    }
+
+    #[test]
+    fn parses_valid_max_attempts_only() {
+        assert_that(parse_max_attempts("3")).is_equal_to(Some(3));
+        assert_that(parse_max_attempts("0")).is_none();
+        assert_that(parse_max_attempts("-1")).is_none();
+    }
+}
@@ -0,0 +1,340 @@
+use crate::delay::{Schedule, ScheduleStrategy};
+#[cfg(any(
+    feature = "async-tokio",
+    feature = "async-std",
+    feature = "async-smol",
+    feature = "async-futures-timer"
+))]
+use crate::delay_executor::DefaultAsyncSleep;
+use crate::delay_executor::ThreadSleep;
+use crate::{LogLevels, NeedsRetry, RetryOptions, retry_with_options};
+#[cfg(any(
+    feature = "async-tokio",
+    feature = "async-std",
+    feature = "async-smol",
+    feature = "async-futures-timer"
+))]
+use crate::{RetryAsyncOptions, retry_async_with_options};
+use std::borrow::Cow;
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+
+/// Decides whether an output of the retried operation should lead to a retry.
+pub trait RetryPredicate<Out> {
+    fn needs_retry(&self, out: &Out) -> bool;
+
+    /// The name of the failure type reported for outputs needing a retry, e.g. as `error.type` (feature
+    /// `opentelemetry`). Defaults to the name of the output type.
+    fn failure_type() -> &'static str
+    where
+        Self: Sized,
+    {
+        std::any::type_name::<Out>()
+    }
+}
+
+/// The default predicate, deferring to the output's `NeedsRetry` implementation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ByNeedsRetry;
+
+impl<Out: NeedsRetry> RetryPredicate<Out> for ByNeedsRetry {
+    fn needs_retry(&self, out: &Out) -> bool {
+        out.needs_retry()
+    }
+
+    fn failure_type() -> &'static str {
+        Out::failure_type()
+    }
+}
+
+impl<Out, F: Fn(&Out) -> bool> RetryPredicate<Out> for F {
+    fn needs_retry(&self, out: &Out) -> bool {
+        self(out)
+    }
+}
+
+/// A reusable description of how to retry an operation: its schedule, limits and retry predicate.
+///
+/// Strategies like `delay::Fixed::of(..).take(..)` are consumed by a single retry loop. A `RetryPolicy` instead stores
+/// a `Schedule` and creates a fresh delay strategy for every call to `retry` or `retry_async`. Policies are `Clone`,
+/// `Send` and `Sync` (given a `Send + Sync` predicate) and can be constructed in `const` contexts, so they can be
+/// stored in a `static` or in shared application state.
+///
+/// ```rust
+/// use try_again::delay::{Jitter, Schedule};
+/// use try_again::{RetryPolicy, StdDuration};
+///
+/// static DB: RetryPolicy = RetryPolicy::new(Schedule::ExponentialBackoff {
+///     initial_delay: StdDuration::from_millis(1),
+///     max_delay: Some(StdDuration::from_millis(4)),
+///     factor: 2,
+///     retries: 3,
+///     jitter: Jitter::None,
+/// });
+///
+/// fn query() -> Result<u32, ()> {
+///     Ok(42)
+/// }
+///
+/// assert_eq!(DB.retry(query), Ok(42));
+/// ```
+///
+/// With the `serde` feature, policies can be (de)serialized. The retry predicate is not part of the representation, a
+/// deserialized policy always uses its default predicate.
+///
+/// ```json
+/// { "schedule": { "type": "fixed", "delay": "250ms", "retries": 3 }, "operation_name": "db" }
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        bound(serialize = "", deserialize = "Pred: Default"),
+        deny_unknown_fields
+    )
+)]
+pub struct RetryPolicy<Pred = ByNeedsRetry> {
+    schedule: Schedule,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    retry_if: Pred,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    operation_name: Option<Cow<'static, str>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    log_levels: Option<LogLevels>,
+}
+
+impl RetryPolicy {
+    pub const fn new(schedule: Schedule) -> Self {
+        Self {
+            schedule,
+            retry_if: ByNeedsRetry,
+            operation_name: None,
+            log_levels: None,
+        }
+    }
+}
+
+impl<Pred> RetryPolicy<Pred> {
+    /// Retries only outputs for which `predicate` returns `true`, instead of relying on `NeedsRetry`.
+    pub fn retry_if<P>(self, predicate: P) -> RetryPolicy<P> {
+        RetryPolicy {
+            schedule: self.schedule,
+            retry_if: predicate,
+            operation_name: self.operation_name,
+            log_levels: self.log_levels,
+        }
+    }
+
+    /// Names the retried operation. The name is used to label the recorded metrics (feature `metrics`).
+    pub fn named(mut self, operation_name: impl Into<Cow<'static, str>>) -> Self {
+        self.operation_name = Some(operation_name.into());
+        self
+    }
+
+    /// Overwrites the globally configured log levels for all retry loops run with this policy.
+    pub const fn with_log_levels(mut self, log_levels: LogLevels) -> Self {
+        self.log_levels = Some(log_levels);
+        self
+    }
+
+    pub const fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    pub fn operation_name(&self) -> Option<&str> {
+        self.operation_name.as_deref()
+    }
+
+    /// Creates a fresh delay strategy following this policy's schedule.
+    pub fn delay_strategy(&self) -> ScheduleStrategy {
+        self.schedule.strategy()
+    }
+
+    /// Retries `operation` according to this policy, sleeping the current thread in between attempts.
+    pub fn retry<Out, Op>(&self, operation: Op) -> Out
+    where
+        Out: Debug,
+        Op: Fn() -> Out,
+        Pred: RetryPredicate<Out>,
+    {
+        let mut options = RetryOptions::builder()
+            .strategy(self.delay_strategy())
+            .executor(ThreadSleep)
+            .build();
+        options.operation_name = self.operation_name.clone();
+        options.log_levels = self.log_levels;
+        retry_with_options(|| Judged::new(operation(), &self.retry_if), options).out
+    }
+
+    /// Retries `operation` according to this policy, sleeping through the `DefaultAsyncSleep` executor in between
+    /// attempts.
+    #[cfg(any(
+        feature = "async-tokio",
+        feature = "async-std",
+        feature = "async-smol",
+        feature = "async-futures-timer"
+    ))]
+    pub async fn retry_async<Out, Op>(&self, operation: Op) -> Out
+    where
+        Out: Debug,
+        Op: AsyncFn() -> Out,
+        Pred: RetryPredicate<Out>,
+    {
+        let mut options = RetryAsyncOptions::builder()
+            .strategy(self.delay_strategy())
+            .executor(DefaultAsyncSleep)
+            .build();
+        options.operation_name = self.operation_name.clone();
+        options.log_levels = self.log_levels;
+        retry_async_with_options(
+            async || Judged::new(operation().await, &self.retry_if),
+            options,
+        )
+        .await
+        .out
+    }
+}
+
+impl<Pred> Debug for RetryPolicy<Pred> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("schedule", &self.schedule)
+            .field("operation_name", &self.operation_name)
+            .field("log_levels", &self.log_levels)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An output, together with the verdict of the policy's predicate `Pred`.
+struct Judged<Out, Pred> {
+    out: Out,
+    needs_retry: bool,
+    _predicate: PhantomData<fn() -> Pred>,
+}
+
+impl<Out, Pred: RetryPredicate<Out>> Judged<Out, Pred> {
+    fn new(out: Out, predicate: &Pred) -> Self {
+        let needs_retry = predicate.needs_retry(&out);
+        Self {
+            out,
+            needs_retry,
+            _predicate: PhantomData,
+        }
+    }
+}
+
+impl<Out, Pred: RetryPredicate<Out>> NeedsRetry for Judged<Out, Pred> {
+    fn needs_retry(&self) -> bool {
+        self.needs_retry
+    }
+
+    fn failure_type() -> &'static str {
+        Pred::failure_type()
+    }
+}
+
+impl<Out: Debug, Pred> Debug for Judged<Out, Pred> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.out.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::IntoStdDuration;
+    use crate::delay::Jitter;
+    use assertr::prelude::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn assert_shareable<T: Clone + Send + Sync + 'static>() {}
+
+    #[test]
+    fn policy_is_clone_send_and_sync() {
+        assert_shareable::<RetryPolicy>();
+        assert_shareable::<RetryPolicy<fn(&Result<(), ()>) -> bool>>();
+    }
+
+    #[test]
+    fn every_call_uses_a_fresh_schedule() {
+        let policy = RetryPolicy::new(Schedule::Fixed {
+            delay: 1.millis(),
+            retries: 2,
+            jitter: Jitter::None,
+        });
+        let calls = AtomicUsize::new(0);
+
+        for _ in 0..2 {
+            let out = policy.retry(|| -> Result<(), ()> {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(())
+            });
+            assert_that(out).is_err();
+        }
+
+        assert_that(calls.load(Ordering::SeqCst)).is_equal_to(6);
+    }
+
+    #[test]
+    fn can_be_created_from_a_textual_schedule() {
+        let spec = "none()*1 then fixed(1ms)*2"
+            .parse::<crate::delay::ScheduleSpec>()
+            .expect("valid schedule");
+        let policy = RetryPolicy::new(spec.into());
+        let calls = AtomicUsize::new(0);
+
+        let out = policy.retry(|| -> Result<(), ()> {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err(())
+        });
+
+        assert_that(out).is_err();
+        assert_that(calls.load(Ordering::SeqCst)).is_equal_to(4);
+    }
+
+    #[test]
+    fn predicate_overrides_needs_retry() {
+        let policy = RetryPolicy::new(Schedule::None { retries: 5 })
+            .retry_if(|out: &Result<u32, u32>| matches!(out, Err(code) if *code < 500));
+        let calls = AtomicUsize::new(0);
+
+        let out = policy.retry(|| -> Result<u32, u32> {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 => Err(429),
+                _ => Err(503),
+            }
+        });
+
+        assert_that(out).is_err().is_equal_to(503);
+        assert_that(calls.load(Ordering::SeqCst)).is_equal_to(2);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn policies_can_be_deserialized() {
+        let json = r#"{
+            "schedule": { "type": "fixed", "delay": "250ms", "retries": 3 },
+            "operation_name": "db",
+            "log_levels": { "on_retry": "off", "on_give_up": "warn" }
+        }"#;
+
+        let policy = serde_json::from_str::<RetryPolicy>(json).expect("deserializable");
+
+        assert_that(policy).is_equal_to(
+            RetryPolicy::new(Schedule::Fixed {
+                delay: 250.millis(),
+                retries: 3,
+                jitter: Jitter::None,
+            })
+            .named("db")
+            .with_log_levels(LogLevels::new(crate::LogLevel::Off, crate::LogLevel::Warn)),
+        );
+    }
+}
@@ -0,0 +1,578 @@
+use crate::{RetryPolicy, StdDuration};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, LazyLock, Mutex, RwLock, Weak};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// A set of named retry policies, loaded from a TOML configuration (feature `registry`).
+///
+/// Every top-level table of the configuration defines one policy, using the `serde` representation of `RetryPolicy`:
+///
+/// ```toml
+/// [db]
+/// schedule = { type = "exponential_backoff", initial_delay = "100ms", max_delay = "5s", retries = 8 }
+///
+/// [http-internal]
+/// schedule = { type = "fixed", delay = "250ms", retries = 3 }
+/// log_levels = { on_retry = "trace", on_give_up = "warn" }
+/// ```
+///
+/// Policies without an explicit `operation_name` are named after their key.
+///
+/// Loaded schedules are validated (see `Schedule::validate`), rejecting e.g. a backoff capped below its initial delay.
+/// Loading is all-or-nothing: If any entry is invalid, all problems are reported and the registry is left unchanged.
+#[derive(Debug, Default)]
+pub struct PolicyRegistry {
+    policies: RwLock<Arc<HashMap<String, RetryPolicy>>>,
+    max_total_delay: Option<StdDuration>,
+}
+
+impl PolicyRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a registry which additionally rejects loaded policies whose delays may add up to more than
+    /// `max_total_delay`.
+    pub fn with_max_total_delay(max_total_delay: StdDuration) -> Self {
+        Self {
+            max_total_delay: Some(max_total_delay),
+            ..Self::default()
+        }
+    }
+
+    /// Returns (a copy of) the policy registered under `name`.
+    pub fn get(&self, name: &str) -> Option<RetryPolicy> {
+        self.snapshot().get(name).cloned()
+    }
+
+    /// The names of all registered policies, in no particular order.
+    pub fn names(&self) -> Vec<String> {
+        self.snapshot().keys().cloned().collect()
+    }
+
+    /// Registers `policy` under `name`, replacing any policy previously registered under that name.
+    pub fn register(&self, name: impl Into<String>, policy: RetryPolicy) {
+        let name = name.into();
+        let policy = named_after(&name, policy);
+        let mut policies = self.policies.write().unwrap_or_else(|err| err.into_inner());
+        Arc::make_mut(&mut policies).insert(name, policy);
+    }
+
+    /// Replaces all registered policies with the policies defined in `config`.
+    pub fn load_toml_str(&self, config: &str) -> Result<(), RegistryError> {
+        let policies = self.parse_toml(config)?;
+        self.replace(policies);
+        Ok(())
+    }
+
+    /// Replaces all registered policies with the policies defined in the TOML file at `path`.
+    pub fn load_toml_file(&self, path: impl AsRef<Path>) -> Result<(), RegistryError> {
+        self.load_toml_str(&read_config(path.as_ref())?)
+    }
+
+    /// Loads the policies from the TOML file at `path` and keeps reloading them whenever the file changes, checking
+    /// for changes every `poll_interval`.
+    ///
+    /// Reloads swap all policies atomically. Retry loops already running keep the schedule they started with, while
+    /// the next lookup returns the reloaded policy. If the changed file is invalid, the error is logged and the
+    /// previously loaded policies are kept. Failures are logged once, until the error changes or a reload succeeds.
+    ///
+    /// Watching stops when the returned `PolicyWatcher` (or the registry) is dropped.
+    pub fn watch_toml_file(
+        self: &Arc<Self>,
+        path: impl Into<PathBuf>,
+        poll_interval: Duration,
+    ) -> Result<PolicyWatcher, RegistryError> {
+        let path = path.into();
+        let config = read_config(&path)?;
+        self.load_toml_str(&config)?;
+        Ok(PolicyWatcher::spawn(
+            Arc::downgrade(self),
+            WatchedFile::new(path, config),
+            poll_interval,
+        ))
+    }
+
+    fn parse_toml(&self, config: &str) -> Result<HashMap<String, RetryPolicy>, RegistryError> {
+        let table = toml::from_str::<toml::Table>(config)
+            .map_err(|err| RegistryError::Syntax(err.to_string()))?;
+
+        let mut policies = HashMap::with_capacity(table.len());
+        let mut invalid = Vec::new();
+        for (name, definition) in table {
+            let policy = definition
+                .try_into::<RetryPolicy>()
+                .map_err(|err| err.message().to_owned())
+                .and_then(|policy| {
+                    let schedule = policy.schedule();
+                    match self.max_total_delay {
+                        Some(max_total_delay) => schedule.validate_within(max_total_delay),
+                        None => schedule.validate(),
+                    }
+                    .map(|()| policy)
+                    .map_err(|err| err.to_string())
+                });
+            match policy {
+                Ok(policy) => {
+                    policies.insert(name.clone(), named_after(&name, policy));
+                }
+                Err(reason) => invalid.push(InvalidPolicy { name, reason }),
+            }
+        }
+
+        match invalid.is_empty() {
+            true => Ok(policies),
+            false => {
+                invalid.sort_by(|a, b| a.name.cmp(&b.name));
+                Err(RegistryError::InvalidPolicies(invalid))
+            }
+        }
+    }
+
+    fn snapshot(&self) -> Arc<HashMap<String, RetryPolicy>> {
+        self.policies
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone()
+    }
+
+    fn replace(&self, policies: HashMap<String, RetryPolicy>) {
+        *self.policies.write().unwrap_or_else(|err| err.into_inner()) = Arc::new(policies);
+    }
+}
+
+/// Reloads the policies of a `PolicyRegistry` whenever its configuration file changes.
+///
+/// Created through `PolicyRegistry::watch_toml_file`. Dropping the watcher stops watching.
+#[derive(Debug)]
+pub struct PolicyWatcher {
+    stop: Arc<(Mutex<bool>, Condvar)>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl PolicyWatcher {
+    fn spawn(
+        registry: Weak<PolicyRegistry>,
+        mut file: WatchedFile,
+        poll_interval: Duration,
+    ) -> Self {
+        let stop = Arc::new((Mutex::new(false), Condvar::new()));
+        let thread = std::thread::spawn({
+            let stop = stop.clone();
+            move || {
+                let (stopped, condvar) = &*stop;
+                let mut stopped = stopped.lock().unwrap_or_else(|err| err.into_inner());
+                loop {
+                    stopped = condvar
+                        .wait_timeout_while(stopped, poll_interval, |stopped| !*stopped)
+                        .unwrap_or_else(|err| err.into_inner())
+                        .0;
+                    let Some(registry) = registry.upgrade().filter(|_| !*stopped) else {
+                        return;
+                    };
+                    file.reload_into(&registry);
+                }
+            }
+        });
+        Self {
+            stop,
+            thread: Some(thread),
+        }
+    }
+
+    /// Stops watching and waits for the watching thread to finish.
+    pub fn stop(mut self) {
+        self.stop_and_join();
+    }
+
+    fn stop_and_join(&mut self) {
+        let (stopped, condvar) = &*self.stop;
+        *stopped.lock().unwrap_or_else(|err| err.into_inner()) = true;
+        condvar.notify_all();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for PolicyWatcher {
+    fn drop(&mut self) {
+        self.stop_and_join();
+    }
+}
+
+/// Outcome of checking a `WatchedFile` for changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Reload {
+    Unchanged,
+    Reloaded,
+    /// Reloading failed with a new error, which was logged.
+    Failed,
+    /// Reloading failed with the error already logged before.
+    StillFailing,
+}
+
+/// The configuration file of a `PolicyWatcher`, remembering what was loaded and logged last.
+#[derive(Debug)]
+struct WatchedFile {
+    path: PathBuf,
+    last_config: String,
+    /// The error loading `last_config`, if it is invalid.
+    config_error: Option<String>,
+    /// The error logged last, cleared once the file is ok again.
+    last_error: Option<String>,
+}
+
+impl WatchedFile {
+    fn new(path: PathBuf, config: String) -> Self {
+        Self {
+            path,
+            last_config: config,
+            config_error: None,
+            last_error: None,
+        }
+    }
+
+    /// Loads the file into `registry` if it changed since the last call.
+    fn reload_into(&mut self, registry: &PolicyRegistry) -> Reload {
+        let result = match read_config(&self.path) {
+            Ok(config) if config == self.last_config => {
+                // The file might have been unreadable in between, so fall back to the state of the unchanged config.
+                self.last_error = self.config_error.clone();
+                return Reload::Unchanged;
+            }
+            Ok(config) => {
+                let result = registry.load_toml_str(&config);
+                self.last_config = config;
+                self.config_error = result.as_ref().err().map(ToString::to_string);
+                result
+            }
+            Err(err) => Err(err),
+        };
+        match result {
+            Ok(()) => {
+                self.last_error = None;
+                crate::logging::policies_reloaded(&self.path);
+                Reload::Reloaded
+            }
+            Err(err) => {
+                let error = err.to_string();
+                if self.last_error.as_ref() == Some(&error) {
+                    return Reload::StillFailing;
+                }
+                crate::logging::policy_reload_failed(&self.path, &err);
+                self.last_error = Some(error);
+                Reload::Failed
+            }
+        }
+    }
+}
+
+static GLOBAL: LazyLock<Arc<PolicyRegistry>> = LazyLock::new(Arc::default);
+
+/// The process-wide `PolicyRegistry`, allowing policies to be looked up by name at any call site.
+///
+/// ```rust
+/// use try_again::{Policies, RetryPolicy, delay::Schedule};
+///
+/// Policies::register("db", RetryPolicy::new(Schedule::None { retries: 2 }));
+///
+/// let policy = Policies::get("db").expect("registered");
+/// assert_eq!(policy.retry(|| -> Result<u32, ()> { Ok(42) }), Ok(42));
+/// ```
+#[derive(Debug)]
+pub struct Policies;
+
+impl Policies {
+    pub fn registry() -> &'static PolicyRegistry {
+        &GLOBAL
+    }
+
+    pub fn get(name: &str) -> Option<RetryPolicy> {
+        GLOBAL.get(name)
+    }
+
+    pub fn register(name: impl Into<String>, policy: RetryPolicy) {
+        GLOBAL.register(name, policy)
+    }
+
+    pub fn load_toml_str(config: &str) -> Result<(), RegistryError> {
+        GLOBAL.load_toml_str(config)
+    }
+
+    pub fn load_toml_file(path: impl AsRef<Path>) -> Result<(), RegistryError> {
+        GLOBAL.load_toml_file(path)
+    }
+
+    pub fn watch_toml_file(
+        path: impl Into<PathBuf>,
+        poll_interval: Duration,
+    ) -> Result<PolicyWatcher, RegistryError> {
+        GLOBAL.watch_toml_file(path, poll_interval)
+    }
+}
+
+/// Error returned when loading policies fails.
+#[derive(Debug)]
+pub enum RegistryError {
+    /// The configuration file could not be read.
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    /// The configuration is no valid TOML.
+    Syntax(String),
+
+    /// The configuration is valid TOML, but contains invalid policy definitions.
+    InvalidPolicies(Vec<InvalidPolicy>),
+}
+
+/// A policy definition which could not be loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidPolicy {
+    pub name: String,
+    pub reason: String,
+}
+
+impl Display for RegistryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegistryError::Io { path, source } => {
+                write!(
+                    f,
+                    "Could not read policies from {}: {source}",
+                    path.display()
+                )
+            }
+            RegistryError::Syntax(message) => write!(f, "Invalid policy configuration: {message}"),
+            RegistryError::InvalidPolicies(invalid) => {
+                write!(f, "Invalid policies:")?;
+                for InvalidPolicy { name, reason } in invalid {
+                    write!(f, "\n- \"{name}\": {reason}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RegistryError::Io { source, .. } => Some(source),
+            RegistryError::Syntax(_) | RegistryError::InvalidPolicies(_) => None,
+        }
+    }
+}
+
+fn read_config(path: &Path) -> Result<String, RegistryError> {
+    std::fs::read_to_string(path).map_err(|source| RegistryError::Io {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+fn named_after(name: &str, policy: RetryPolicy) -> RetryPolicy {
+    match policy.operation_name() {
+        Some(_) => policy,
+        None => policy.named(name.to_owned()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::IntoStdDuration;
+    use crate::delay::{Jitter, Schedule};
+    use assertr::prelude::*;
+
+    const CONFIG: &str = r#"
+        [db]
+        schedule = { type = "exponential_backoff", initial_delay = "100ms", max_delay = "5s", retries = 8 }
+
+        [http-internal]
+        schedule = { type = "fixed", delay = "250ms", retries = 3 }
+        operation_name = "http"
+    "#;
+
+    #[test]
+    fn loads_named_policies() {
+        let registry = PolicyRegistry::new();
+        registry.load_toml_str(CONFIG).expect("valid config");
+
+        let db = registry.get("db").expect("db policy");
+        assert_that(db.schedule().clone()).is_equal_to(Schedule::ExponentialBackoff {
+            initial_delay: 100.millis(),
+            max_delay: Some(5.secs()),
+            factor: 2,
+            retries: 8,
+            jitter: Jitter::None,
+        });
+        assert_that(db.operation_name()).is_equal_to(Some("db"));
+
+        let http = registry.get("http-internal").expect("http-internal policy");
+        assert_that(http.operation_name()).is_equal_to(Some("http"));
+
+        assert_that(registry.get("s3")).is_none();
+    }
+
+    #[test]
+    fn reports_all_invalid_entries_and_keeps_previous_policies() {
+        let registry = PolicyRegistry::new();
+        registry.load_toml_str(CONFIG).expect("valid config");
+
+        let err = registry
+            .load_toml_str(
+                r#"
+                [db]
+                schedule = { type = "fixed", delay = "1 fortnight", retries = 3 }
+
+                [s3]
+                schedule = { type = "fixed", delay = "1s", retires = 3 }
+
+                [ok]
+                schedule = { type = "none", retries = 1 }
+                "#,
+            )
+            .expect_err("invalid config");
+
+        let RegistryError::InvalidPolicies(invalid) = err else {
+            panic!("expected invalid policies, got {err:?}");
+        };
+        assert_that(invalid.len()).is_equal_to(2);
+        assert_that(invalid[0].name.as_str()).is_equal_to("db");
+        assert_that(invalid[0].reason.as_str()).contains("unknown unit \"fortnight\"");
+        assert_that(invalid[1].name.as_str()).is_equal_to("s3");
+        assert_that(invalid[1].reason.as_str()).contains("unknown field `retires`");
+
+        assert_that(registry.get("db")).is_some();
+        assert_that(registry.get("ok")).is_none();
+    }
+
+    #[test]
+    fn rejects_unintended_schedules() {
+        let registry = PolicyRegistry::with_max_total_delay(10.secs());
+
+        let err = registry
+            .load_toml_str(
+                r#"
+                [capped-below-initial]
+                schedule = { type = "exponential_backoff", initial_delay = "5s", max_delay = "1s", retries = 3 }
+
+                [too-slow]
+                schedule = { type = "fixed", delay = "5s", retries = 3 }
+                "#,
+            )
+            .expect_err("invalid config");
+
+        assert_that(err.to_string()).is_equal_to(
+            "Invalid policies:\n\
+             - \"capped-below-initial\": cap of 1s is below the initial delay of 5s\n\
+             - \"too-slow\": worst-case total delay exceeds the limit of 10s"
+                .to_owned(),
+        );
+    }
+
+    #[test]
+    fn reports_syntax_errors() {
+        let err = PolicyRegistry::new()
+            .load_toml_str("[db")
+            .expect_err("invalid config");
+
+        assert_that(matches!(err, RegistryError::Syntax(_))).is_true();
+    }
+
+    #[test]
+    fn policies_can_be_registered_programmatically() {
+        let registry = PolicyRegistry::new();
+        registry.register("db", RetryPolicy::new(Schedule::None { retries: 1 }));
+
+        let db = registry.get("db").expect("db policy");
+        assert_that(db.schedule().clone()).is_equal_to(Schedule::None { retries: 1 });
+        assert_that(db.operation_name()).is_equal_to(Some("db"));
+        assert_that(registry.names()).contains_exactly(["db".to_owned()]);
+    }
+
+    #[test]
+    fn watched_files_are_reloaded_on_change() {
+        let path = std::env::temp_dir().join(format!(
+            "try-again-watched-policies-{}.toml",
+            std::process::id()
+        ));
+        // Replaces the file atomically, so the watcher never observes a truncated (empty, but valid) file.
+        let write = |config: &str| {
+            let staged = path.with_extension("toml.staged");
+            std::fs::write(&staged, config).expect("writable temp file");
+            std::fs::rename(&staged, &path).expect("renamable temp file");
+        };
+        let write_retries = |retries: usize| {
+            write(&format!(
+                "[db]\nschedule = {{ type = \"none\", retries = {retries} }}\n"
+            ));
+        };
+        let retries = |registry: &PolicyRegistry| {
+            registry.get("db").map(|policy| policy.schedule().retries())
+        };
+
+        write_retries(1);
+        let registry = Arc::new(PolicyRegistry::new());
+        let watcher = registry
+            .watch_toml_file(&path, Duration::from_millis(5))
+            .expect("valid config");
+        assert_that(retries(&registry)).is_equal_to(Some(1));
+
+        let in_flight = registry.get("db").expect("db policy");
+
+        write_retries(2);
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while retries(&registry) != Some(2) && std::time::Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert_that(retries(&registry)).is_equal_to(Some(2));
+        assert_that(in_flight.schedule().retries()).is_equal_to(1);
+
+        watcher.stop();
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn reload_failures_are_logged_once_per_error() {
+        let path = std::env::temp_dir().join(format!(
+            "try-again-reloaded-policies-{}.toml",
+            std::process::id()
+        ));
+        let valid = "[db]\nschedule = { type = \"none\", retries = 1 }\n";
+        std::fs::write(&path, valid).expect("writable temp file");
+        let registry = PolicyRegistry::new();
+        registry.load_toml_str(valid).expect("valid config");
+        let mut file = WatchedFile::new(path.clone(), valid.to_owned());
+
+        assert_that(file.reload_into(&registry)).is_equal_to(Reload::Unchanged);
+
+        std::fs::write(&path, "[db").expect("writable temp file");
+        assert_that(file.reload_into(&registry)).is_equal_to(Reload::Failed);
+        assert_that(file.reload_into(&registry)).is_equal_to(Reload::Unchanged);
+        assert_that(registry.get("db").is_some()).is_true();
+
+        std::fs::remove_file(&path).expect("removable temp file");
+        assert_that(file.reload_into(&registry)).is_equal_to(Reload::Failed);
+        assert_that(file.reload_into(&registry)).is_equal_to(Reload::StillFailing);
+
+        std::fs::write(&path, "[db").expect("writable temp file");
+        assert_that(file.reload_into(&registry)).is_equal_to(Reload::Unchanged);
+        std::fs::remove_file(&path).expect("removable temp file");
+        assert_that(file.reload_into(&registry)).is_equal_to(Reload::Failed);
+
+        std::fs::write(&path, valid.replace('1', "2")).expect("writable temp file");
+        assert_that(file.reload_into(&registry)).is_equal_to(Reload::Reloaded);
+        assert_that(registry.get("db").map(|policy| policy.schedule().retries()))
+            .is_equal_to(Some(2));
+
+        std::fs::remove_file(&path).expect("removable temp file");
+        assert_that(file.reload_into(&registry)).is_equal_to(Reload::Failed);
+    }
+}
@@ -0,0 +1,202 @@
+use crate::clock::Clock;
+use std::borrow::Cow;
+
+/// Label value used for retry loops not given an operation name.
+#[cfg(feature = "metrics")]
+const UNNAMED: &str = "unnamed";
+
+/// Records the metrics of a single retry loop through the `metrics` facade (feature `metrics`).
+///
+/// All metrics are labeled with `operation`, the name given to the retry loop.
+///
+/// - `try_again_attempts_total` (counter): Every invocation of the operation.
+/// - `try_again_retries_total` (counter): Every failed invocation followed by a retry.
+/// - `try_again_give_ups_total` (counter): Every retry loop ending without success.
+/// - `try_again_attempts_per_call` (histogram): The number of invocations per retry loop.
+/// - `try_again_elapsed_seconds` (histogram): The total time spent in a retry loop.
+pub(crate) struct RetryMetrics {
+    #[cfg(feature = "metrics")]
+    operation: metrics::SharedString,
+    #[cfg(feature = "metrics")]
+    started_at: std::time::Instant,
+}
+
+impl RetryMetrics {
+    #[allow(unused_variables)]
+    pub(crate) fn start(operation_name: Option<&Cow<'static, str>>, clock: &dyn Clock) -> Self {
+        Self {
+            #[cfg(feature = "metrics")]
+            operation: match operation_name {
+                Some(Cow::Borrowed(name)) => metrics::SharedString::const_str(name),
+                Some(Cow::Owned(name)) => metrics::SharedString::from_owned(name.clone()),
+                None => metrics::SharedString::const_str(UNNAMED),
+            },
+            #[cfg(feature = "metrics")]
+            started_at: clock.now(),
+        }
+    }
+
+    pub(crate) fn attempt(&self) {
+        #[cfg(feature = "metrics")]
+        metrics::counter!("try_again_attempts_total", "operation" => self.operation.clone())
+            .increment(1);
+    }
+
+    pub(crate) fn retry(&self) {
+        #[cfg(feature = "metrics")]
+        metrics::counter!("try_again_retries_total", "operation" => self.operation.clone())
+            .increment(1);
+    }
+
+    pub(crate) fn give_up(&self) {
+        #[cfg(feature = "metrics")]
+        metrics::counter!("try_again_give_ups_total", "operation" => self.operation.clone())
+            .increment(1);
+    }
+
+    #[allow(unused_variables)]
+    pub(crate) fn finish(self, attempts: usize, clock: &dyn Clock) {
+        #[cfg(feature = "metrics")]
+        {
+            metrics::histogram!("try_again_attempts_per_call", "operation" => self.operation.clone())
+                .record(attempts as f64);
+            metrics::histogram!("try_again_elapsed_seconds", "operation" => self.operation)
+                .record(clock.elapsed_since(self.started_at).as_secs_f64());
+        }
+    }
+}
+
+#[cfg(all(test, feature = "metrics"))]
+mod test {
+    use super::*;
+    use crate::IntoStdDuration;
+    use crate::clock::{ManualClock, SystemClock};
+    use assertr::prelude::*;
+    use metrics::{
+        Counter, CounterFn, Gauge, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder,
+        SharedString, Unit,
+    };
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Default)]
+    struct Recorded {
+        counters: Mutex<Vec<(String, String, u64)>>,
+        histograms: Mutex<Vec<(String, String, f64)>>,
+    }
+
+    struct Handle {
+        key: Key,
+        recorded: Arc<Recorded>,
+    }
+
+    impl Handle {
+        fn name_and_operation(&self) -> (String, String) {
+            let operation = self
+                .key
+                .labels()
+                .find(|label| label.key() == "operation")
+                .map(|label| label.value().to_owned())
+                .unwrap_or_default();
+            (self.key.name().to_owned(), operation)
+        }
+    }
+
+    impl CounterFn for Handle {
+        fn increment(&self, value: u64) {
+            let (name, operation) = self.name_and_operation();
+            self.recorded
+                .counters
+                .lock()
+                .expect("not poisoned")
+                .push((name, operation, value));
+        }
+
+        fn absolute(&self, _value: u64) {}
+    }
+
+    impl HistogramFn for Handle {
+        fn record(&self, value: f64) {
+            let (name, operation) = self.name_and_operation();
+            self.recorded
+                .histograms
+                .lock()
+                .expect("not poisoned")
+                .push((name, operation, value));
+        }
+    }
+
+    #[derive(Default)]
+    struct TestRecorder {
+        recorded: Arc<Recorded>,
+    }
+
+    impl Recorder for TestRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            Counter::from_arc(Arc::new(Handle {
+                key: key.clone(),
+                recorded: self.recorded.clone(),
+            }))
+        }
+
+        fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::noop()
+        }
+
+        fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::from_arc(Arc::new(Handle {
+                key: key.clone(),
+                recorded: self.recorded.clone(),
+            }))
+        }
+    }
+
+    #[test]
+    fn records_counters_and_histograms_labeled_with_the_operation_name() {
+        let recorder = TestRecorder::default();
+        let clock = ManualClock::new();
+
+        metrics::with_local_recorder(&recorder, || {
+            let metrics = RetryMetrics::start(Some(&Cow::Borrowed("db")), &clock);
+            metrics.attempt();
+            metrics.retry();
+            clock.advance(1500.millis());
+            metrics.attempt();
+            metrics.give_up();
+            metrics.finish(2, &clock);
+        });
+
+        let counters = recorder.recorded.counters.lock().expect("not poisoned");
+        assert_that(counters.as_slice()).contains_exactly([
+            ("try_again_attempts_total".to_owned(), "db".to_owned(), 1),
+            ("try_again_retries_total".to_owned(), "db".to_owned(), 1),
+            ("try_again_attempts_total".to_owned(), "db".to_owned(), 1),
+            ("try_again_give_ups_total".to_owned(), "db".to_owned(), 1),
+        ]);
+
+        let histograms = recorder.recorded.histograms.lock().expect("not poisoned");
+        assert_that(histograms.len()).is_equal_to(2);
+        assert_that(histograms[0].clone()).is_equal_to((
+            "try_again_attempts_per_call".to_owned(),
+            "db".to_owned(),
+            2.0,
+        ));
+        assert_that(histograms[1].0.as_str()).is_equal_to("try_again_elapsed_seconds");
+        assert_that(histograms[1].2).is_equal_to(1.5);
+    }
+
+    #[test]
+    fn uses_placeholder_label_for_unnamed_operations() {
+        let recorder = TestRecorder::default();
+
+        metrics::with_local_recorder(&recorder, || {
+            RetryMetrics::start(None, &SystemClock).attempt();
+        });
+
+        let counters = recorder.recorded.counters.lock().expect("not poisoned");
+        assert_that(counters[0].1.as_str()).is_equal_to(UNNAMED);
+    }
+}
@@ -0,0 +1,91 @@
+//! Graceful shutdown of retry loops on `SIGINT` (Ctrl-C) and `SIGTERM` (feature `signals`, Unix only).
+//!
+//! `install` registers a handler for both signals. The first signal received cancels a process-wide `CancelHandle`,
+//! which stops every retry loop sleeping through it: The current delay is cut short and the loop returns the last
+//! output instead of the process being killed mid-attempt. Any further signal terminates the process as usual, so a
+//! second Ctrl-C still works when something else hangs.
+//!
+//! **The handler is process-wide and stays installed for the lifetime of the process.** From the first call of
+//! `install`, `cancellable_thread_sleep` or `cancel_signal` on, the first `SIGINT` or `SIGTERM` no longer terminates
+//! the process, even while no retry loop is running: It only cancels the handle, so a Ctrl-C between retry loops
+//! seemingly does nothing and every later loop using the handle stops after its first attempt. Only call these
+//! functions in processes which check the handle (`CancelHandle::is_cancelled`) to shut down, or accept that a second
+//! signal is required to terminate them.
+//!
+//! - Synchronous retry loops use `cancellable_thread_sleep` as their executor, together with `retry_with_cancellation`
+//!   (or `retry_with_options`).
+//! - Asynchronous retry loops use `cancel_signal` with `retry_async_with_cancellation`.
+//!
+//! ```rust,no_run
+//! use try_again::{IntoStdDuration, RetryOptions, delay, retry_with_cancellation, signals};
+//!
+//! let outcome = retry_with_cancellation(
+//!     || -> Result<(), ()> { Err(()) },
+//!     RetryOptions::builder()
+//!         .strategy(delay::Fixed::of(30.secs()).take(5))
+//!         .executor(signals::cancellable_thread_sleep().expect("signal handler to be installable"))
+//!         .build(),
+//! );
+//! ```
+
+use crate::delay_executor::{CancelHandle, CancellableThreadSleep};
+use signal_hook::consts::{SIGINT, SIGTERM};
+use signal_hook::iterator::Signals;
+use std::sync::{Mutex, OnceLock};
+
+static SHUTDOWN: OnceLock<CancelHandle> = OnceLock::new();
+static INSTALL: Mutex<()> = Mutex::new(());
+
+/// Installs the handler for `SIGINT` and `SIGTERM`, returning the handle cancelled by the first of them.
+///
+/// Calling this again returns the same handle without installing another handler. The handler is never removed, so the
+/// first signal only cancels the handle from now on, whether or not a retry loop is running (see the module docs).
+pub fn install() -> std::io::Result<CancelHandle> {
+    let _installing = INSTALL
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(handle) = SHUTDOWN.get() {
+        return Ok(handle.clone());
+    }
+
+    let mut signals = Signals::new([SIGINT, SIGTERM])?;
+    let handle = CancelHandle::default();
+    std::thread::Builder::new()
+        .name("try-again-signals".to_owned())
+        .spawn({
+            let handle = handle.clone();
+            move || {
+                for signal in signals.forever() {
+                    match handle.is_cancelled() {
+                        false => {
+                            crate::logging::shutdown_requested(signal);
+                            handle.cancel();
+                        }
+                        true => {
+                            let _ = signal_hook::low_level::emulate_default_handler(signal);
+                        }
+                    }
+                }
+            }
+        })?;
+
+    Ok(SHUTDOWN.get_or_init(|| handle).clone())
+}
+
+/// An executor sleeping like `ThreadSleep`, which is woken up and cancelled by `SIGINT` or `SIGTERM`.
+///
+/// Installs the process-wide signal handler if necessary, so the first signal no longer terminates the process (see
+/// `install`).
+pub fn cancellable_thread_sleep() -> std::io::Result<CancellableThreadSleep> {
+    install().map(CancellableThreadSleep::with_cancel_handle)
+}
+
+/// A signal for `retry_async_with_cancellation`, completing on `SIGINT` or `SIGTERM`. Running attempts are awaited.
+///
+/// Installs the process-wide signal handler if necessary, so the first signal no longer terminates the process, even
+/// after the retry loop finished (see `install`).
+#[cfg(feature = "async")]
+pub fn cancel_signal()
+-> std::io::Result<crate::CancelSignal<impl Future<Output = ()> + Send + 'static>> {
+    install().map(|handle| crate::CancelSignal::on(handle.cancelled()))
+}
@@ -0,0 +1,11 @@
+//! Utilities for testing code which retries operations (feature `test-util`).
+//!
+//! - `VirtualClock` is a delay executor which advances simulated time instead of sleeping and records every requested
+//!   delay, so tests run instantly and can assert the exact schedule.
+//! - `ScriptedOperation` is a fake operation returning a predefined sequence of outputs and counting its calls.
+
+mod scripted_operation;
+mod virtual_clock;
+
+pub use scripted_operation::ScriptedOperation;
+pub use virtual_clock::VirtualClock;
@@ -0,0 +1,116 @@
+use std::collections::VecDeque;
+use std::sync::{Mutex, MutexGuard};
+
+/// A fake operation returning a predefined sequence of outputs, e.g. to simulate a flaky service.
+///
+/// Use `as_fn` for the sync and `as_async_fn` for the async retry loops. Each call returns the next scripted output
+/// and is counted. Calling the operation more often than scripted panics, as the retry loop then made more attempts
+/// than the test anticipated.
+///
+/// ```rust
+/// use try_again::test_util::ScriptedOperation;
+/// use try_again::{delay, retry};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Timeout;
+///
+/// let operation = ScriptedOperation::new([Err(Timeout), Err(Timeout), Ok(42)]);
+///
+/// let outcome = retry(operation.as_fn()).delayed_by(delay::None.take(5));
+///
+/// assert_eq!(outcome, Ok(42));
+/// assert_eq!(operation.calls(), 3);
+/// ```
+#[derive(Debug)]
+pub struct ScriptedOperation<Out> {
+    state: Mutex<State<Out>>,
+}
+
+#[derive(Debug)]
+struct State<Out> {
+    outputs: VecDeque<Out>,
+    calls: usize,
+}
+
+impl<Out> ScriptedOperation<Out> {
+    /// Creates an operation returning `outputs`, one per call, in order.
+    pub fn new(outputs: impl IntoIterator<Item = Out>) -> Self {
+        Self {
+            state: Mutex::new(State {
+                outputs: outputs.into_iter().collect(),
+                calls: 0,
+            }),
+        }
+    }
+
+    /// Returns the next scripted output.
+    ///
+    /// # Panics
+    ///
+    /// If all scripted outputs were already returned.
+    pub fn call(&self) -> Out {
+        let (output, calls) = {
+            let mut state = self.state();
+            state.calls += 1;
+            (state.outputs.pop_front(), state.calls)
+        };
+        output.unwrap_or_else(|| {
+            panic!(
+                "ScriptedOperation called {calls} times, but only {} outputs were scripted",
+                calls - 1
+            )
+        })
+    }
+
+    /// This operation as a sync function, accepted by `retry` and `retry_with_options`.
+    pub fn as_fn(&self) -> impl Fn() -> Out + '_ {
+        || self.call()
+    }
+
+    /// This operation as an async function, accepted by `retry_async` and `retry_async_with_options`.
+    #[cfg(feature = "async")]
+    pub fn as_async_fn(&self) -> impl AsyncFn() -> Out + '_ {
+        async || self.call()
+    }
+
+    /// The number of times this operation was called so far.
+    pub fn calls(&self) -> usize {
+        self.state().calls
+    }
+
+    /// The number of scripted outputs not yet returned.
+    pub fn remaining(&self) -> usize {
+        self.state().outputs.len()
+    }
+
+    fn state(&self) -> MutexGuard<'_, State<Out>> {
+        // The state is never left inconsistent, so a poisoned lock can safely be recovered.
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use assertr::prelude::*;
+
+    #[test]
+    fn returns_the_scripted_outputs_in_order() {
+        let operation = ScriptedOperation::new([1, 2]);
+
+        assert_that(operation.call()).is_equal_to(1);
+        assert_that(operation.remaining()).is_equal_to(1);
+        assert_that(operation.call()).is_equal_to(2);
+        assert_that(operation.calls()).is_equal_to(2);
+    }
+
+    #[test]
+    #[should_panic(expected = "ScriptedOperation called 2 times, but only 1 outputs were scripted")]
+    fn panics_when_called_more_often_than_scripted() {
+        let operation = ScriptedOperation::new([1]);
+        operation.call();
+        operation.call();
+    }
+}
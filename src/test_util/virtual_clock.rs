@@ -0,0 +1,217 @@
+use crate::StdDuration;
+use crate::clock::Clock;
+#[cfg(feature = "async")]
+use crate::delay_executor::{AsyncDelayExecutor, SendAsyncDelayExecutor};
+use crate::delay_executor::{DelayExecutor, remaining};
+use crate::overrides;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Instant;
+
+/// A delay executor advancing simulated time instead of sleeping.
+///
+/// Every requested delay is recorded and returns immediately, for both the sync and async retry loops. Clones share
+/// the same clock, so a clone can be handed to the retry loop while the original is kept for assertions.
+///
+/// Delays are recorded as slept for by the provided executors, i.e. with the time scale of `overrides` applied. Delays anchored
+/// at the start of an attempt (see `DelayAnchor::AttemptStart`) only advance the simulated time up to their deadline.
+///
+/// The clock also implements `Clock`, reporting the simulated time. Pass it to `RetryOptionsBuilder::clock` as well to
+/// measure elapsed time (e.g. the `elapsed_ms` of attempts) in simulated time.
+///
+/// ```rust
+/// use try_again::test_util::VirtualClock;
+/// use try_again::{IntoStdDuration, RetryOptions, delay, retry_with_options};
+///
+/// let clock = VirtualClock::new();
+///
+/// let outcome = retry_with_options(
+///     || -> Result<(), ()> { Err(()) },
+///     RetryOptions::builder()
+///         .strategy(delay::ExponentialBackoff::of_initial_delay(1.secs()).uncapped().take(3))
+///         .executor(clock.clone())
+///         .build(),
+/// );
+///
+/// assert_eq!(outcome, Err(()));
+/// assert_eq!(clock.recorded_delays(), [1.secs(), 2.secs(), 4.secs()]);
+/// assert_eq!(clock.elapsed(), 7.secs());
+/// ```
+#[derive(Debug, Clone)]
+pub struct VirtualClock {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug)]
+struct State {
+    started_at: Instant,
+    elapsed: StdDuration,
+    recorded_delays: Vec<StdDuration>,
+}
+
+impl VirtualClock {
+    /// Creates a clock at simulated time zero, without any recorded delays.
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State {
+                started_at: Instant::now(),
+                elapsed: StdDuration::ZERO,
+                recorded_delays: Vec::new(),
+            })),
+        }
+    }
+
+    /// The simulated time passed since the clock was created.
+    pub fn elapsed(&self) -> StdDuration {
+        self.state().elapsed
+    }
+
+    /// All delays requested so far, in order.
+    pub fn recorded_delays(&self) -> Vec<StdDuration> {
+        self.state().recorded_delays.clone()
+    }
+
+    /// Advances the simulated time without recording a delay, e.g. to simulate time spent in the operation.
+    pub fn advance(&self, by: StdDuration) {
+        let mut state = self.state();
+        state.elapsed = state.elapsed.saturating_add(by);
+    }
+
+    fn record(&self, delay: StdDuration) {
+        let mut state = self.state();
+        state.elapsed = state.elapsed.saturating_add(delay);
+        state.recorded_delays.push(delay);
+    }
+
+    /// Records `delay`, advancing the simulated time to `delay` after `since` as measured by `clock`, unless that
+    /// point already passed.
+    fn record_since(&self, delay: StdDuration, since: Instant, clock: &dyn Clock) {
+        let remaining = remaining(delay, since, clock);
+        let mut state = self.state();
+        state.elapsed = state.elapsed.saturating_add(remaining);
+        state.recorded_delays.push(delay);
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        // The state is never left inconsistent, so a poisoned lock can safely be recovered.
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for VirtualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for VirtualClock {
+    fn now(&self) -> Instant {
+        let state = self.state();
+        saturating_add(state.started_at, state.elapsed)
+    }
+}
+
+/// Adds `duration` to `instant`, saturating at the latest point in time `Instant` can represent.
+fn saturating_add(instant: Instant, duration: StdDuration) -> Instant {
+    instant.checked_add(duration).unwrap_or_else(|| {
+        // The maximum is platform-specific, so approach it by halving the remaining duration.
+        let (mut latest, mut step) = (instant, duration);
+        while !step.is_zero() {
+            match latest.checked_add(step) {
+                Some(later) => latest = later,
+                None => step /= 2,
+            }
+        }
+        latest
+    })
+}
+
+impl<Delay: Into<StdDuration>> DelayExecutor<Delay> for VirtualClock {
+    fn delay_by(&self, delay: Delay) {
+        self.record(overrides::scaled(delay.into()));
+    }
+
+    fn delay_since(&self, delay: Delay, since: Instant, clock: &dyn Clock) {
+        self.record_since(overrides::scaled(delay.into()), since, clock);
+    }
+}
+
+#[cfg(feature = "async")]
+impl<Delay: Into<StdDuration>> AsyncDelayExecutor<Delay> for VirtualClock {
+    async fn delay_by(&self, delay: Delay) {
+        self.record(overrides::scaled(delay.into()));
+    }
+
+    async fn delay_since(&self, delay: Delay, since: Instant, clock: &dyn Clock) {
+        self.record_since(overrides::scaled(delay.into()), since, clock);
+    }
+}
+
+#[cfg(feature = "async")]
+impl<Delay: Into<StdDuration>> SendAsyncDelayExecutor<Delay> for VirtualClock {
+    fn delay_by(&self, delay: Delay) -> impl Future<Output = ()> + Send {
+        self.record(overrides::scaled(delay.into()));
+        std::future::ready(())
+    }
+
+    fn delay_since(
+        &self,
+        delay: Delay,
+        since: Instant,
+        clock: &dyn Clock,
+    ) -> impl Future<Output = ()> + Send {
+        self.record_since(overrides::scaled(delay.into()), since, clock);
+        std::future::ready(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::IntoStdDuration;
+    use assertr::prelude::*;
+
+    #[test]
+    fn clones_share_the_same_clock() {
+        let clock = VirtualClock::new();
+        DelayExecutor::delay_by(&clock.clone(), 1.secs());
+        clock.advance(500.millis());
+
+        assert_that(clock.recorded_delays()).contains_exactly([1.secs()]);
+        assert_that(clock.elapsed()).is_equal_to(1500.millis());
+    }
+
+    #[test]
+    fn reports_the_simulated_time_as_clock() {
+        let clock = VirtualClock::new();
+        let start = clock.now();
+        DelayExecutor::delay_by(&clock, 2.secs());
+
+        assert_that(clock.elapsed_since(start)).is_equal_to(2.secs());
+    }
+
+    #[test]
+    fn delays_anchored_in_the_past_only_advance_to_their_deadline() {
+        let clock = VirtualClock::new();
+        let start = clock.now();
+        clock.advance(2.secs());
+
+        DelayExecutor::delay_since(&clock, 5.secs(), start, &clock);
+        assert_that(clock.elapsed()).is_equal_to(5.secs());
+
+        DelayExecutor::delay_since(&clock, 1.secs(), start, &clock);
+        assert_that(clock.elapsed()).is_equal_to(5.secs());
+        assert_that(clock.recorded_delays()).contains_exactly([5.secs(), 1.secs()]);
+    }
+
+    #[test]
+    fn anchored_delays_beyond_the_representable_time_are_not_shortened() {
+        let clock = VirtualClock::new();
+        let start = clock.now();
+
+        DelayExecutor::delay_since(&clock, StdDuration::MAX, start, &clock);
+        assert_that(clock.elapsed()).is_equal_to(StdDuration::MAX);
+        assert_that(clock.now()).is_greater_than(start);
+    }
+}
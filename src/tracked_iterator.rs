@@ -1,12 +1,12 @@
 use std::marker::PhantomData;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum Finite {}
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum Infinite {}
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum Unknown {}
 
 pub type FiniteIterator<I> = TrackedIterator<I, Finite>;
@@ -16,7 +16,7 @@ pub type InfiniteIterator<I> = TrackedIterator<I, Infinite>;
 pub type UnknownIterator<I> = TrackedIterator<I, Unknown>;
 
 /// Wrapper for iterators with finiteness tracking.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TrackedIterator<I: Iterator, F> {
     pub(crate) inner: I,
     pub(crate) _marker: PhantomData<F>,
@@ -1,3 +1,10 @@
+#![cfg(any(
+    feature = "async-tokio",
+    feature = "async-std",
+    feature = "async-smol",
+    feature = "async-futures-timer"
+))]
+
 mod retry_async {
     use assertr::assert_that;
     use assertr::prelude::*;
@@ -117,7 +124,9 @@ mod retry_async_with_options {
     use std::sync::Arc;
     use std::sync::atomic::{AtomicI32, Ordering};
     use try_again::{
-        IntoStdDuration, RetryAsyncOptions, delay, delay_executor::TokioSleep,
+        IntoStdDuration, RetryAsyncOptions, StdDuration, delay,
+        delay_executor::{DefaultAsyncSleep, DynAsyncDelayExecutor},
+        delay_strategy::{BoxedDelayStrategy, DelayStrategy},
         retry_async_with_options,
     };
 
@@ -126,11 +135,10 @@ mod retry_async_with_options {
         let test = async || -> Result<(), ()> { Ok(()) };
         let out = retry_async_with_options(
             test,
-            RetryAsyncOptions {
-                delay_strategy: delay::None.take(0),
-                delay_executor: TokioSleep,
-                _marker: Default::default(),
-            },
+            RetryAsyncOptions::builder()
+                .strategy(delay::None.take(0))
+                .executor(DefaultAsyncSleep)
+                .build(),
         )
         .await;
         assert_that(out).is_ok().is_equal_to(());
@@ -143,11 +151,10 @@ mod retry_async_with_options {
         }
         let out = retry_async_with_options(
             test,
-            RetryAsyncOptions {
-                delay_strategy: delay::None.take(0),
-                delay_executor: TokioSleep,
-                _marker: Default::default(),
-            },
+            RetryAsyncOptions::builder()
+                .strategy(delay::None.take(0))
+                .executor(DefaultAsyncSleep)
+                .build(),
         )
         .await;
         assert_that(out).is_ok().is_equal_to(());
@@ -165,11 +172,10 @@ mod retry_async_with_options {
         let out = {
             retry_async_with_options(
                 async || successful(counter.clone()).await,
-                RetryAsyncOptions {
-                    delay_strategy: delay::Fixed::of(50.millis()).take(3),
-                    delay_executor: TokioSleep,
-                    _marker: Default::default(),
-                },
+                RetryAsyncOptions::builder()
+                    .strategy(delay::Fixed::of(50.millis()).take(3))
+                    .executor(DefaultAsyncSleep)
+                    .build(),
             )
             .await
         };
@@ -193,11 +199,10 @@ mod retry_async_with_options {
             let counter = counter.clone();
             retry_async_with_options(
                 async || erroneous(counter.clone()).await,
-                RetryAsyncOptions {
-                    delay_strategy: delay::Fixed::of(50.millis()).take(3),
-                    delay_executor: TokioSleep,
-                    _marker: Default::default(),
-                },
+                RetryAsyncOptions::builder()
+                    .strategy(delay::Fixed::of(50.millis()).take(3))
+                    .executor(DefaultAsyncSleep)
+                    .build(),
             )
             .await
         };
@@ -207,4 +212,356 @@ mod retry_async_with_options {
             .with_subject_name("Function")
             .is_equal_to(4);
     }
+
+    #[tokio::test]
+    async fn accepts_strategies_and_executors_selected_at_runtime() {
+        struct Client {
+            options:
+                fn() -> RetryAsyncOptions<StdDuration, BoxedDelayStrategy, DynAsyncDelayExecutor>,
+        }
+
+        let client = Client {
+            options: || {
+                RetryAsyncOptions::builder()
+                    .strategy(delay::Fixed::of(1.millis()).take(2).boxed())
+                    .executor(DynAsyncDelayExecutor::new(DefaultAsyncSleep))
+                    .build()
+            },
+        };
+
+        let counter = AtomicI32::new(0);
+        let out = retry_async_with_options(
+            async || -> Result<(), ()> {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Err(())
+            },
+            (client.options)(),
+        )
+        .await;
+
+        assert_that(out).is_err();
+        assert_that(counter.load(Ordering::SeqCst)).is_equal_to(3);
+    }
+}
+
+mod retry_policy {
+    use assertr::assert_that;
+    use assertr::prelude::*;
+    use std::sync::atomic::{AtomicI32, Ordering};
+    use try_again::delay::{Jitter, Schedule};
+    use try_again::{IntoStdDuration, RetryPolicy};
+
+    #[tokio::test]
+    async fn every_call_uses_a_fresh_schedule() {
+        let policy = RetryPolicy::new(Schedule::Fixed {
+            delay: 1.millis(),
+            retries: 2,
+            jitter: Jitter::None,
+        });
+        let counter = AtomicI32::new(0);
+
+        for _ in 0..2 {
+            let out = policy
+                .retry_async(async || -> Result<(), ()> {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    Err(())
+                })
+                .await;
+            assert_that(out).is_err();
+        }
+
+        assert_that(counter.load(Ordering::SeqCst))
+            .with_subject_name("Function")
+            .is_equal_to(6);
+    }
+}
+
+mod retry_async_send {
+    use assertr::assert_that;
+    use assertr::prelude::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicI32, Ordering};
+    use try_again::delay_executor::{DefaultAsyncSleep, SendAsyncDelayExecutor};
+    use try_again::{
+        IntoStdDuration, RetryAsyncOptions, StdDuration, delay, retry_async_send,
+        retry_async_send_with_options,
+    };
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn can_be_spawned_onto_a_multi_threaded_runtime() {
+        let counter = Arc::new(AtomicI32::new(0));
+
+        let handle = tokio::spawn({
+            let counter = counter.clone();
+            retry_async_send(move || {
+                let counter = counter.clone();
+                async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    tokio::task::yield_now().await;
+                    Err::<(), i32>(42)
+                }
+            })
+            .named("spawned")
+            .delayed_by(delay::Fixed::of(1.millis()).take(2))
+        });
+
+        let out = handle.await.expect("task not to panic");
+        assert_that(out).is_err().is_equal_to(42);
+        assert_that(counter.load(Ordering::SeqCst)).is_equal_to(3);
+    }
+
+    /// Must compile without knowing the concrete executor.
+    fn spawn_with<Exec>(delay_executor: Exec) -> tokio::task::JoinHandle<Result<u32, ()>>
+    where
+        Exec: SendAsyncDelayExecutor<StdDuration> + 'static,
+    {
+        let options = RetryAsyncOptions::builder()
+            .strategy(delay::None.take(1))
+            .executor(delay_executor)
+            .build();
+        tokio::spawn(retry_async_send_with_options(|| async { Ok(42) }, options))
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn can_be_spawned_from_generic_code() {
+        let out = spawn_with(DefaultAsyncSleep)
+            .await
+            .expect("task not to panic");
+        assert_that(out).is_ok().is_equal_to(42);
+    }
+}
+
+mod runtime_executors {
+    use assertr::assert_that;
+    use assertr::prelude::*;
+    use std::time::Instant;
+    use try_again::delay_executor::AsyncDelayExecutor;
+    use try_again::{
+        IntoStdDuration, RetryAsyncOptions, StdDuration, delay, retry_async_with_options,
+    };
+
+    /// Retries twice, 20ms apart, returning the elapsed time.
+    async fn retry_twice_with(delay_executor: impl AsyncDelayExecutor<StdDuration>) -> StdDuration {
+        let start = Instant::now();
+        let out = retry_async_with_options(
+            async || -> Result<(), ()> { Err(()) },
+            RetryAsyncOptions::builder()
+                .strategy(delay::Fixed::of(20.millis()).take(2))
+                .executor(delay_executor)
+                .build(),
+        )
+        .await;
+        assert_that(out).is_err();
+        start.elapsed()
+    }
+
+    #[cfg(feature = "async-std")]
+    #[test]
+    fn async_std_sleep_waits_for_the_delay() {
+        let elapsed =
+            async_std::task::block_on(retry_twice_with(try_again::delay_executor::AsyncStdSleep));
+        assert_that(elapsed).is_greater_or_equal_to(40.millis());
+    }
+
+    #[cfg(feature = "async-smol")]
+    #[test]
+    fn smol_sleep_waits_for_the_delay() {
+        let elapsed = async_io::block_on(retry_twice_with(try_again::delay_executor::SmolSleep));
+        assert_that(elapsed).is_greater_or_equal_to(40.millis());
+    }
+
+    #[cfg(feature = "async-futures-timer")]
+    #[tokio::test]
+    async fn futures_timer_sleep_waits_for_the_delay() {
+        let elapsed = retry_twice_with(try_again::delay_executor::FuturesTimerSleep).await;
+        assert_that(elapsed).is_greater_or_equal_to(40.millis());
+    }
+
+    #[tokio::test]
+    async fn default_async_sleep_is_selected_by_feature() {
+        let elapsed = retry_twice_with(try_again::delay_executor::DefaultAsyncSleep).await;
+        assert_that(elapsed).is_greater_or_equal_to(40.millis());
+    }
+}
+
+#[cfg(feature = "async-tokio")]
+mod delay_anchor {
+    use assertr::assert_that;
+    use assertr::prelude::*;
+    use std::sync::Mutex;
+    use try_again::clock::TokioClock;
+    use try_again::delay_executor::TokioSleep;
+    use try_again::{
+        DelayAnchor, IntoStdDuration, RetryAsyncOptions, StdDuration, delay,
+        retry_async_send_with_options, retry_async_with_options,
+    };
+
+    #[tokio::test(start_paused = true)]
+    async fn starts_attempts_on_a_fixed_schedule_when_anchored_at_the_attempt_start() {
+        let start = tokio::time::Instant::now();
+        let attempt_starts = Mutex::new(Vec::<StdDuration>::new());
+
+        let out = retry_async_with_options(
+            async || -> Result<(), ()> {
+                attempt_starts
+                    .lock()
+                    .expect("lock not to be poisoned")
+                    .push(start.elapsed());
+                tokio::time::sleep(2.secs()).await;
+                Err(())
+            },
+            RetryAsyncOptions::builder()
+                .strategy(delay::Fixed::of(5.secs()).take(2))
+                .executor(TokioSleep)
+                .clock(TokioClock)
+                .delay_anchor(DelayAnchor::AttemptStart)
+                .build(),
+        )
+        .await;
+
+        assert_that(out).is_err();
+        assert_that(
+            attempt_starts
+                .into_inner()
+                .expect("lock not to be poisoned"),
+        )
+        .contains_exactly([0.secs(), 5.secs(), 10.secs()]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn retries_immediately_when_an_attempt_outlasts_its_delay() {
+        let start = tokio::time::Instant::now();
+
+        let out = retry_async_send_with_options(
+            || async {
+                tokio::time::sleep(8.secs()).await;
+                Err::<(), ()>(())
+            },
+            RetryAsyncOptions::builder()
+                .strategy(delay::Fixed::of(5.secs()).take(2))
+                .executor(TokioSleep)
+                .clock(TokioClock)
+                .delay_anchor(DelayAnchor::AttemptStart)
+                .build(),
+        )
+        .await;
+
+        assert_that(out).is_err();
+        assert_that(start.elapsed()).is_equal_to(24.secs());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn delays_start_after_the_attempt_by_default() {
+        let start = tokio::time::Instant::now();
+
+        let out = retry_async_with_options(
+            async || -> Result<(), ()> {
+                tokio::time::sleep(2.secs()).await;
+                Err(())
+            },
+            RetryAsyncOptions::builder()
+                .strategy(delay::Fixed::of(5.secs()).take(2))
+                .executor(TokioSleep)
+                .clock(TokioClock)
+                .build(),
+        )
+        .await;
+
+        assert_that(out).is_err();
+        assert_that(start.elapsed()).is_equal_to(16.secs());
+    }
+}
+
+#[cfg(feature = "async-tokio")]
+mod retry_async_with_cancellation {
+    use assertr::assert_that;
+    use assertr::prelude::*;
+    use std::sync::atomic::{AtomicI32, Ordering};
+    use try_again::delay_executor::TokioSleep;
+    use try_again::{
+        CancelSignal, Cancelled, IntoStdDuration, RetryAsyncOptions, delay,
+        retry_async_with_cancellation,
+    };
+
+    #[tokio::test(start_paused = true)]
+    async fn interrupts_the_delay_and_returns_the_last_output() {
+        let counter = AtomicI32::new(0);
+        let start = tokio::time::Instant::now();
+
+        let out = retry_async_with_cancellation(
+            async || -> Result<(), i32> { Err(counter.fetch_add(1, Ordering::SeqCst)) },
+            RetryAsyncOptions::builder()
+                .strategy(delay::Fixed::of(30.secs()).take(5))
+                .executor(TokioSleep)
+                .build(),
+            CancelSignal::on(tokio::time::sleep(45.secs())),
+        )
+        .await;
+
+        assert_that(out).is_err().is_equal_to(Cancelled {
+            attempts: 2,
+            last_output: Some(Err(1)),
+        });
+        assert_that(start.elapsed()).is_equal_to(45.secs());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn awaits_a_running_attempt_by_default() {
+        let out = retry_async_with_cancellation(
+            async || -> Result<(), ()> {
+                tokio::time::sleep(10.secs()).await;
+                Err(())
+            },
+            RetryAsyncOptions::builder()
+                .strategy(delay::Fixed::of(30.secs()).take(5))
+                .executor(TokioSleep)
+                .build(),
+            CancelSignal::on(tokio::time::sleep(5.secs())),
+        )
+        .await;
+
+        assert_that(out).is_err().is_equal_to(Cancelled {
+            attempts: 1,
+            last_output: Some(Err(())),
+        });
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn interrupts_a_running_attempt_when_configured() {
+        let start = tokio::time::Instant::now();
+
+        let out = retry_async_with_cancellation(
+            async || -> Result<(), ()> {
+                tokio::time::sleep(10.secs()).await;
+                Err(())
+            },
+            RetryAsyncOptions::builder()
+                .strategy(delay::Fixed::of(30.secs()).take(5))
+                .executor(TokioSleep)
+                .build(),
+            CancelSignal::on(tokio::time::sleep(5.secs())).interrupting_attempts(),
+        )
+        .await;
+
+        assert_that(out).is_err().is_equal_to(Cancelled {
+            attempts: 1,
+            last_output: None,
+        });
+        assert_that(start.elapsed()).is_equal_to(5.secs());
+    }
+
+    #[tokio::test]
+    async fn returns_the_output_when_not_cancelled() {
+        let out = retry_async_with_cancellation(
+            async || -> Result<(), i32> { Err(42) },
+            RetryAsyncOptions::builder()
+                .strategy(delay::None.take(2))
+                .executor(TokioSleep)
+                .build(),
+            CancelSignal::on(std::future::pending()),
+        )
+        .await;
+
+        assert_that(out).is_ok().is_err().is_equal_to(42);
+    }
 }
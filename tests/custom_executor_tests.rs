@@ -0,0 +1,67 @@
+#![cfg(feature = "async")]
+
+use assertr::assert_that;
+use assertr::prelude::*;
+use std::sync::{Arc, Mutex};
+use try_again::delay_executor::{AsyncDelayExecutor, SendAsyncDelayExecutor};
+use try_again::{IntoStdDuration, StdDuration, delay, retry_async, retry_async_send};
+
+/// Records the requested delays instead of sleeping. Usable without any runtime feature.
+#[derive(Debug, Clone, Default)]
+struct RecordingSleep {
+    delays: Arc<Mutex<Vec<StdDuration>>>,
+}
+
+impl RecordingSleep {
+    fn delays(&self) -> Vec<StdDuration> {
+        self.delays.lock().expect("lock not to be poisoned").clone()
+    }
+}
+
+impl AsyncDelayExecutor<StdDuration> for RecordingSleep {
+    async fn delay_by(&self, delay: StdDuration) {
+        self.delays
+            .lock()
+            .expect("lock not to be poisoned")
+            .push(delay);
+    }
+}
+
+impl SendAsyncDelayExecutor<StdDuration> for RecordingSleep {
+    fn delay_by(&self, delay: StdDuration) -> impl Future<Output = ()> + Send {
+        AsyncDelayExecutor::delay_by(self, delay)
+    }
+}
+
+#[tokio::test]
+async fn retry_async_sleeps_through_the_executor_given_by_with_executor() {
+    let executor = RecordingSleep::default();
+
+    let out = retry_async(async || -> Result<(), ()> { Err(()) })
+        .with_executor(executor.clone())
+        .delayed_by(
+            delay::ExponentialBackoff::of_initial_delay(10.millis())
+                .uncapped()
+                .take(3),
+        )
+        .await;
+
+    assert_that(out).is_err();
+    assert_that(executor.delays()).contains_exactly([10.millis(), 20.millis(), 40.millis()]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn retry_async_send_sleeps_through_the_executor_given_by_with_executor() {
+    let executor = RecordingSleep::default();
+
+    let handle = tokio::spawn(
+        retry_async_send(|| async { Err::<(), ()>(()) })
+            .named("spawned")
+            .with_executor(executor.clone())
+            .delayed_by(delay::Fixed::of(5.millis()).take(2)),
+    );
+
+    let out = handle.await.expect("task not to panic");
+    assert_that(out).is_err();
+    assert_that(executor.delays()).contains_exactly([5.millis(), 5.millis()]);
+}
@@ -0,0 +1,75 @@
+use assertr::assert_that;
+use assertr::prelude::*;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use try_again::delay_executor::DelayExecutor;
+use try_again::{
+    IntoStdDuration, RetryOptions, StdDuration, delay, overrides, retry, retry_with_options,
+};
+
+/// A custom executor, only recording the delays it would sleep for after applying the time scale.
+#[derive(Debug, Clone, Default)]
+struct Recording {
+    delays: Arc<Mutex<Vec<StdDuration>>>,
+}
+
+impl DelayExecutor<StdDuration> for Recording {
+    fn delay_by(&self, delay: StdDuration) {
+        self.delays
+            .lock()
+            .expect("not poisoned")
+            .push(overrides::scaled(delay));
+    }
+}
+
+/// Overrides are process-wide. Everything touching them lives in a single test to not interfere with each other.
+#[test]
+fn overrides_apply_to_all_retry_loops() {
+    overrides::set_time_scale(0.0);
+    let counter = AtomicI32::new(0);
+    let started_at = Instant::now();
+    let out = retry(|| -> Result<(), ()> {
+        counter.fetch_add(1, Ordering::SeqCst);
+        Err(())
+    })
+    .delayed_by(delay::Fixed::of(10.secs()).take(3));
+    assert_that(out).is_err();
+    assert_that(counter.load(Ordering::SeqCst))
+        .with_subject_name("Function")
+        .is_equal_to(4);
+    assert_that(started_at.elapsed()).is_less_than(1.secs());
+
+    overrides::set_time_scale(0.5);
+    let recording = Recording::default();
+    let out = retry_with_options(
+        || -> Result<(), ()> { Err(()) },
+        RetryOptions::builder()
+            .strategy(delay::Fixed::of(10.secs()).take(2))
+            .executor(recording.clone())
+            .build(),
+    );
+    assert_that(out).is_err();
+    assert_that(recording.delays.lock().expect("not poisoned").clone())
+        .contains_exactly([5.secs(), 5.secs()]);
+
+    overrides::set_time_scale(2.0);
+    assert_that(overrides::scaled(StdDuration::MAX)).is_equal_to(StdDuration::MAX);
+
+    overrides::set_max_attempts(Some(2));
+    let counter = AtomicI32::new(0);
+    let out = retry(|| -> Result<(), ()> {
+        counter.fetch_add(1, Ordering::SeqCst);
+        Err(())
+    })
+    .delayed_by(delay::None.take(5));
+    assert_that(out).is_err();
+    assert_that(counter.load(Ordering::SeqCst))
+        .with_subject_name("Function")
+        .is_equal_to(2);
+
+    overrides::set_max_attempts(None);
+    overrides::set_time_scale(1.0);
+    assert_that(overrides::max_attempts()).is_none();
+    assert_that(overrides::time_scale()).is_equal_to(1.0);
+}
@@ -0,0 +1,68 @@
+#![cfg(all(unix, feature = "signals"))]
+
+//! Signals affect the whole test process, so everything is verified within a single test.
+
+use assertr::assert_that;
+use assertr::prelude::*;
+use signal_hook::consts::SIGTERM;
+use std::time::Instant;
+use try_again::{
+    Cancelled, IntoStdDuration, RetryOptions, delay, retry_with_cancellation, signals,
+};
+
+#[test]
+fn sigterm_cancels_sync_and_async_retry_loops() {
+    let handle = signals::install().expect("signal handler to be installable");
+    assert_that(handle.is_cancelled()).is_false();
+    let start = Instant::now();
+
+    let sync_worker = std::thread::spawn(|| {
+        retry_with_cancellation(
+            || -> Result<(), &str> { Err("sync") },
+            RetryOptions::builder()
+                .strategy(delay::Fixed::of(30.secs()).take(5))
+                .executor(signals::cancellable_thread_sleep().expect("installed handler"))
+                .build(),
+        )
+    });
+
+    #[cfg(feature = "async-tokio")]
+    let async_worker = std::thread::spawn(|| {
+        use try_again::delay_executor::TokioSleep;
+        use try_again::{RetryAsyncOptions, retry_async_with_cancellation};
+
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("runtime")
+            .block_on(retry_async_with_cancellation(
+                async || -> Result<(), &str> { Err("async") },
+                RetryAsyncOptions::builder()
+                    .strategy(delay::Fixed::of(30.secs()).take(5))
+                    .executor(TokioSleep)
+                    .build(),
+                signals::cancel_signal().expect("installed handler"),
+            ))
+    });
+
+    std::thread::sleep(100.millis());
+    signal_hook::low_level::raise(SIGTERM).expect("signal to be sent");
+
+    let out = sync_worker.join().expect("sync worker not to panic");
+    assert_that(out).is_err().is_equal_to(Cancelled {
+        attempts: 1,
+        last_output: Some(Err("sync")),
+    });
+
+    #[cfg(feature = "async-tokio")]
+    {
+        let out = async_worker.join().expect("async worker not to panic");
+        assert_that(out).is_err().is_equal_to(Cancelled {
+            attempts: 1,
+            last_output: Some(Err("async")),
+        });
+    }
+
+    assert_that(handle.is_cancelled()).is_true();
+    assert_that(start.elapsed()).is_less_than(5.secs());
+}
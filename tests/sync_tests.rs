@@ -3,7 +3,7 @@ mod retry {
     use assertr::prelude::*;
     use std::sync::Arc;
     use std::sync::atomic::{AtomicI32, Ordering};
-    use try_again::{IntoStdDuration, delay, retry};
+    use try_again::{IntoStdDuration, LogLevel, LogLevels, delay, retry};
 
     #[test]
     fn accepts_closure() {
@@ -55,28 +55,117 @@ mod retry {
             .with_subject_name("Function")
             .is_equal_to(4);
     }
+
+    #[test]
+    fn accepts_custom_log_levels() {
+        let out = retry(|| -> Result<(), ()> { Err(()) })
+            .with_log_levels(LogLevels::new(LogLevel::Off, LogLevel::Warn))
+            .delayed_by(delay::None.take(2));
+        assert_that(out).is_err();
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn accepts_strategies_yielding_time_durations() {
+        let strategy = delay::Fixed::of(time::Duration::milliseconds(1))
+            .take(2)
+            .map(|delay| time::Duration::try_from(delay).expect("representable"));
+
+        let out = retry(|| -> Result<(), ()> { Err(()) }).delayed_by(strategy);
+        assert_that(out).is_err();
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn accepts_strategies_yielding_chrono_time_deltas() {
+        use try_again::IntoTimeDelta;
+
+        let strategy = delay::ExponentialBackoff::of_initial_delay(IntoTimeDelta::millis(1))
+            .capped_at(chrono::TimeDelta::milliseconds(2))
+            .take(2)
+            .map(|delay| chrono::TimeDelta::from_std(delay).expect("representable"));
+
+        let out = retry(|| -> Result<(), ()> { Err(()) }).delayed_by(strategy);
+        assert_that(out).is_err();
+    }
 }
 
 mod retry_with_options {
     use assertr::assert_that;
     use assertr::prelude::*;
-    use std::marker::PhantomData;
     use std::sync::Arc;
     use std::sync::atomic::{AtomicI32, Ordering};
     use try_again::{
-        IntoStdDuration, RetryOptions, delay, delay_executor::ThreadSleep, retry_with_options,
+        DelayAnchor, IntoStdDuration, LogLevel, LogLevels, RetryOptions, StdDuration,
+        clock::{Clock, ManualClock},
+        delay,
+        delay_executor::{BoxedDelayExecutor, DelayExecutor, ThreadSleep},
+        delay_strategy::{BoxedDelayStrategy, DelayStrategy},
+        retry_with_options,
     };
 
+    #[test]
+    fn accepts_strategies_and_executors_selected_at_runtime() {
+        fn options_from_config(
+            fixed: bool,
+        ) -> RetryOptions<StdDuration, BoxedDelayStrategy, BoxedDelayExecutor> {
+            let strategy = match fixed {
+                true => delay::Fixed::of(1.millis()).take(2).boxed(),
+                false => delay::None.take(2).boxed(),
+            };
+            RetryOptions::builder()
+                .strategy(strategy)
+                .executor(Box::new(ThreadSleep) as BoxedDelayExecutor)
+                .build()
+        }
+
+        for fixed in [true, false] {
+            let counter = AtomicI32::new(0);
+            let out = retry_with_options(
+                || -> Result<(), ()> {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    Err(())
+                },
+                options_from_config(fixed),
+            );
+            assert_that(out).is_err();
+            assert_that(counter.load(Ordering::SeqCst)).is_equal_to(3);
+        }
+    }
+
+    #[test]
+    fn builder_sets_all_options() {
+        let clock = ManualClock::new();
+        let options = RetryOptions::builder()
+            .strategy(delay::Fixed::of(1.millis()).take(2))
+            .operation_name("db")
+            .log_levels(
+                LogLevels::new(LogLevel::Trace, LogLevel::Warn).with_on_cancel(LogLevel::Debug),
+            )
+            .clock(clock.clone())
+            .build();
+
+        assert_that(options.operation_name.as_deref()).is_equal_to(Some("db"));
+        assert_that(options.log_levels.map(|levels| levels.on_give_up))
+            .is_equal_to(Some(LogLevel::Warn));
+        assert_that(options.log_levels.map(|levels| levels.on_cancel))
+            .is_equal_to(Some(LogLevel::Debug));
+        clock.advance(1.secs());
+        assert_that(options.clock.now()).is_equal_to(clock.now());
+
+        let out = retry_with_options(|| -> Result<(), i32> { Err(42) }, options);
+        assert_that(out).is_err().is_equal_to(42);
+    }
+
     #[test]
     fn accepts_closure() {
         let test = || -> Result<(), ()> { Ok(()) };
         let out = retry_with_options(
             test,
-            RetryOptions {
-                delay_strategy: delay::None.take(0),
-                delay_executor: ThreadSleep,
-                _marker: PhantomData,
-            },
+            RetryOptions::builder()
+                .strategy(delay::None.take(0))
+                .executor(ThreadSleep)
+                .build(),
         );
         assert_that(out).is_ok().is_equal_to(());
     }
@@ -88,11 +177,10 @@ mod retry_with_options {
         }
         let out = retry_with_options(
             test,
-            RetryOptions {
-                delay_strategy: delay::None.take(0),
-                delay_executor: ThreadSleep,
-                _marker: PhantomData,
-            },
+            RetryOptions::builder()
+                .strategy(delay::None.take(0))
+                .executor(ThreadSleep)
+                .build(),
         );
         assert_that(out).is_ok().is_equal_to(());
     }
@@ -109,11 +197,10 @@ mod retry_with_options {
         let out = {
             retry_with_options(
                 || successful(counter.clone()),
-                RetryOptions {
-                    delay_strategy: delay::None.take(3),
-                    delay_executor: ThreadSleep,
-                    _marker: PhantomData,
-                },
+                RetryOptions::builder()
+                    .strategy(delay::None.take(3))
+                    .executor(ThreadSleep)
+                    .build(),
             )
         };
 
@@ -135,11 +222,10 @@ mod retry_with_options {
         let out = {
             retry_with_options(
                 || erroneous(counter.clone()),
-                RetryOptions {
-                    delay_strategy: delay::Fixed::of(50.millis()).take(3),
-                    delay_executor: ThreadSleep,
-                    _marker: PhantomData,
-                },
+                RetryOptions::builder()
+                    .strategy(delay::Fixed::of(50.millis()).take(3))
+                    .executor(ThreadSleep)
+                    .build(),
             )
         };
 
@@ -148,4 +234,176 @@ mod retry_with_options {
             .with_subject_name("Function")
             .is_equal_to(4);
     }
+
+    #[test]
+    fn thread_sleep_does_not_drift_when_delays_are_anchored_at_the_attempt_start() {
+        let start = std::time::Instant::now();
+
+        let out = retry_with_options(
+            || -> Result<(), ()> {
+                std::thread::sleep(100.millis());
+                Err(())
+            },
+            RetryOptions::builder()
+                .strategy(delay::Fixed::of(150.millis()).take(2))
+                .executor(ThreadSleep)
+                .delay_anchor(DelayAnchor::AttemptStart)
+                .build(),
+        );
+
+        // Attempts start at 0ms, 150ms and 300ms. Delays starting after each attempt would take 600ms in total.
+        assert_that(out).is_err();
+        assert_that(start.elapsed()).is_greater_or_equal_to(400.millis());
+        assert_that(start.elapsed()).is_less_than(550.millis());
+    }
+
+    #[test]
+    fn accepts_delay_types_unrelated_to_durations() {
+        #[derive(Debug, Clone)]
+        struct Ticks(u32);
+
+        #[derive(Debug, Clone, Default)]
+        struct CountTicks(Arc<AtomicI32>);
+
+        #[derive(Debug)]
+        struct TwoTicks(u32);
+
+        impl DelayStrategy<Ticks> for TwoTicks {
+            fn next_delay(&mut self) -> Option<Ticks> {
+                self.0 = self.0.checked_sub(1)?;
+                Some(Ticks(2))
+            }
+        }
+
+        impl DelayExecutor<Ticks> for CountTicks {
+            fn delay_by(&self, by: Ticks) {
+                self.0.fetch_add(by.0 as i32, Ordering::SeqCst);
+            }
+        }
+
+        let ticks = CountTicks::default();
+        let out = retry_with_options(
+            || -> Result<(), ()> { Err(()) },
+            RetryOptions::builder()
+                .strategy(TwoTicks(3))
+                .executor(ticks.clone())
+                .build(),
+        );
+
+        assert_that(out).is_err();
+        assert_that(ticks.0.load(Ordering::SeqCst)).is_equal_to(6);
+    }
+
+    #[test]
+    fn anchored_delays_measure_the_time_passed_through_the_configured_clock() {
+        let clock = ManualClock::new();
+        let attempt_clock = clock.clone();
+        let start = std::time::Instant::now();
+
+        let out = retry_with_options(
+            move || -> Result<(), ()> {
+                attempt_clock.advance(10.secs());
+                Err(())
+            },
+            RetryOptions::builder()
+                .strategy(delay::Fixed::of(10.secs()).take(2))
+                .executor(ThreadSleep)
+                .delay_anchor(DelayAnchor::AttemptStart)
+                .clock(clock)
+                .build(),
+        );
+
+        // Every attempt takes the full delay in the time of the clock, so no delay is left to sleep for.
+        assert_that(out).is_err();
+        assert_that(start.elapsed()).is_less_than(1.secs());
+    }
+}
+
+mod retry_with_cancellation {
+    use assertr::assert_that;
+    use assertr::prelude::*;
+    use std::sync::atomic::{AtomicI32, Ordering};
+    use std::time::Instant;
+    use try_again::delay_executor::{CancellableThreadSleep, ThreadSleep};
+    use try_again::{
+        Cancelled, IntoStdDuration, RetryOptions, delay, retry_with_cancellation,
+        retry_with_options,
+    };
+
+    #[test]
+    fn cancelling_from_another_thread_interrupts_the_delay() {
+        let sleep = CancellableThreadSleep::new();
+        let cancel = sleep.cancel_handle();
+        let start = Instant::now();
+
+        let worker = std::thread::spawn(move || {
+            let counter = AtomicI32::new(0);
+            retry_with_cancellation(
+                || -> Result<(), i32> { Err(counter.fetch_add(1, Ordering::SeqCst)) },
+                RetryOptions::builder()
+                    .strategy(delay::Fixed::of(30.secs()).take(5))
+                    .executor(sleep)
+                    .build(),
+            )
+        });
+        std::thread::sleep(50.millis());
+        cancel.cancel();
+
+        let out = worker.join().expect("worker not to panic");
+        assert_that(out).is_err().is_equal_to(Cancelled {
+            attempts: 1,
+            last_output: Some(Err(0)),
+        });
+        assert_that(start.elapsed()).is_less_than(5.secs());
+    }
+
+    #[test]
+    fn cancelled_executors_stop_retry_loops_after_the_first_attempt() {
+        let sleep = CancellableThreadSleep::new();
+        sleep.cancel_handle().cancel();
+        let counter = AtomicI32::new(0);
+
+        let out = retry_with_options(
+            || -> Result<(), ()> {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Err(())
+            },
+            RetryOptions::builder()
+                .strategy(delay::Fixed::of(30.secs()).take(5))
+                .executor(sleep)
+                .build(),
+        );
+
+        assert_that(out).is_err();
+        assert_that(counter.load(Ordering::SeqCst)).is_equal_to(1);
+    }
+
+    #[test]
+    fn exhaustion_is_reported_as_the_last_output() {
+        let out = retry_with_cancellation(
+            || -> Result<(), i32> { Err(42) },
+            RetryOptions::builder()
+                .strategy(delay::Fixed::of(1.millis()).take(2))
+                .executor(ThreadSleep)
+                .build(),
+        );
+
+        assert_that(out).is_ok().is_err().is_equal_to(42);
+    }
+
+    #[test]
+    fn uncancelled_executor_sleeps_like_thread_sleep() {
+        let start = Instant::now();
+
+        let out = retry_with_cancellation(
+            || -> Result<(), ()> { Err(()) },
+            RetryOptions::builder()
+                .strategy(delay::Fixed::of(20.millis()).take(2))
+                .executor(CancellableThreadSleep::new())
+                .build(),
+        );
+
+        assert_that(out).is_ok().is_err();
+        assert_that(start.elapsed()).is_greater_or_equal_to(40.millis());
+    }
 }
@@ -0,0 +1,245 @@
+#![cfg(feature = "test-util")]
+
+mod virtual_clock {
+    use assertr::assert_that;
+    use assertr::prelude::*;
+    use try_again::test_util::VirtualClock;
+    use try_again::{DelayAnchor, IntoStdDuration, RetryOptions, delay, retry_with_options};
+
+    #[test]
+    fn records_the_delays_of_the_sync_retry_loop() {
+        let clock = VirtualClock::new();
+
+        let out = retry_with_options(
+            || -> Result<(), ()> { Err(()) },
+            RetryOptions::builder()
+                .strategy(
+                    delay::ExponentialBackoff::of_initial_delay(10.secs())
+                        .capped_at(30.secs())
+                        .take(4),
+                )
+                .executor(clock.clone())
+                .build(),
+        );
+
+        assert_that(out).is_err();
+        assert_that(clock.recorded_delays()).contains_exactly([
+            10.secs(),
+            20.secs(),
+            30.secs(),
+            30.secs(),
+        ]);
+        assert_that(clock.elapsed()).is_equal_to(90.secs());
+    }
+
+    #[test]
+    fn records_nothing_when_the_first_attempt_succeeds() {
+        let clock = VirtualClock::new();
+
+        let out = retry_with_options(
+            || -> Result<(), ()> { Ok(()) },
+            RetryOptions::builder()
+                .strategy(delay::Fixed::of(1.secs()).take(3))
+                .executor(clock.clone())
+                .build(),
+        );
+
+        assert_that(out).is_ok();
+        assert_that(clock.recorded_delays()).is_empty();
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn records_the_delays_of_the_async_retry_loop() {
+        use try_again::retry_async;
+
+        let clock = VirtualClock::new();
+
+        let out = retry_async(async || -> Result<(), ()> { Err(()) })
+            .with_executor(clock.clone())
+            .delayed_by(delay::Fixed::of(3600.secs()).take(2))
+            .await;
+
+        assert_that(out).is_err();
+        assert_that(clock.recorded_delays()).contains_exactly([3600.secs(), 3600.secs()]);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn records_the_delays_of_a_spawned_retry_loop() {
+        use try_again::retry_async_send;
+
+        let clock = VirtualClock::new();
+
+        let out = tokio::spawn(
+            retry_async_send(|| async { Err::<(), ()>(()) })
+                .with_executor(clock.clone())
+                .delayed_by(delay::Fixed::of(5.secs()).take(3)),
+        )
+        .await
+        .expect("task not to panic");
+
+        assert_that(out).is_err();
+        assert_that(clock.elapsed()).is_equal_to(15.secs());
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn an_already_completed_signal_cancels_the_async_retry_loop() {
+        use try_again::{
+            CancelSignal, Cancelled, RetryAsyncOptions, retry_async_with_cancellation,
+        };
+
+        let clock = VirtualClock::new();
+
+        let out = retry_async_with_cancellation(
+            async || -> Result<(), ()> { Err(()) },
+            RetryAsyncOptions::builder()
+                .strategy(delay::None.take(5))
+                .executor(clock.clone())
+                .build(),
+            CancelSignal::on(std::future::ready(())),
+        )
+        .await;
+
+        assert_that(out).is_err().is_equal_to(Cancelled {
+            attempts: 1,
+            last_output: Some(Err(())),
+        });
+        assert_that(clock.recorded_delays()).is_empty();
+    }
+
+    #[test]
+    fn saturates_instead_of_overflowing_on_huge_delays() {
+        let clock = VirtualClock::new();
+
+        let out = retry_with_options(
+            || -> Result<(), ()> { Err(()) },
+            RetryOptions::builder()
+                .strategy(
+                    delay::ExponentialBackoff::of_initial_delay(1.secs())
+                        .uncapped()
+                        .take(80),
+                )
+                .executor(clock.clone())
+                .clock(clock.clone())
+                .build(),
+        );
+
+        assert_that(out).is_err();
+        assert_that(clock.recorded_delays()).has_length(80);
+    }
+
+    fn retry_slow_operation(clock: &VirtualClock, delay_anchor: DelayAnchor) {
+        let out = retry_with_options(
+            || -> Result<(), ()> {
+                clock.advance(2.secs());
+                Err(())
+            },
+            RetryOptions::builder()
+                .strategy(delay::Fixed::of(5.secs()).take(2))
+                .executor(clock.clone())
+                .clock(clock.clone())
+                .delay_anchor(delay_anchor)
+                .build(),
+        );
+        assert_that(out).is_err();
+    }
+
+    #[test]
+    fn delays_drift_by_the_runtime_of_attempts_by_default() {
+        let clock = VirtualClock::new();
+
+        retry_slow_operation(&clock, DelayAnchor::AttemptEnd);
+
+        assert_that(clock.elapsed()).is_equal_to(16.secs());
+    }
+
+    #[test]
+    fn delays_anchored_at_the_attempt_start_do_not_drift() {
+        let clock = VirtualClock::new();
+
+        retry_slow_operation(&clock, DelayAnchor::AttemptStart);
+
+        assert_that(clock.recorded_delays()).contains_exactly([5.secs(), 5.secs()]);
+        assert_that(clock.elapsed()).is_equal_to(12.secs());
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn delays_of_the_async_retry_loop_anchored_at_the_attempt_start_do_not_drift() {
+        use try_again::{RetryAsyncOptions, retry_async_with_options};
+
+        let clock = VirtualClock::new();
+
+        let out = retry_async_with_options(
+            async || -> Result<(), ()> {
+                clock.advance(7.secs());
+                Err(())
+            },
+            RetryAsyncOptions::builder()
+                .strategy(delay::Fixed::of(5.secs()).take(2))
+                .executor(clock.clone())
+                .clock(clock.clone())
+                .delay_anchor(DelayAnchor::AttemptStart)
+                .build(),
+        )
+        .await;
+
+        assert_that(out).is_err();
+        assert_that(clock.elapsed()).is_equal_to(21.secs());
+    }
+}
+
+mod scripted_operation {
+    use assertr::assert_that;
+    use assertr::prelude::*;
+    use try_again::test_util::{ScriptedOperation, VirtualClock};
+    use try_again::{IntoStdDuration, RetryOptions, delay, retry, retry_with_options};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Timeout;
+
+    #[test]
+    fn retries_until_the_scripted_success() {
+        let operation = ScriptedOperation::new([Err(Timeout), Err(Timeout), Ok(42)]);
+        let clock = VirtualClock::new();
+
+        let out = retry_with_options(
+            operation.as_fn(),
+            RetryOptions::builder()
+                .strategy(delay::Fixed::of(1.secs()).take(5))
+                .executor(clock.clone())
+                .build(),
+        );
+
+        assert_that(out).is_ok().is_equal_to(42);
+        assert_that(operation.calls()).is_equal_to(3);
+        assert_that(operation.remaining()).is_equal_to(0);
+        assert_that(clock.recorded_delays()).contains_exactly([1.secs(), 1.secs()]);
+    }
+
+    #[test]
+    #[should_panic(expected = "ScriptedOperation called 3 times, but only 2 outputs were scripted")]
+    fn panics_when_the_retry_loop_makes_more_attempts_than_scripted() {
+        let operation = ScriptedOperation::new([Err::<(), _>(Timeout), Err(Timeout)]);
+
+        let _ = retry(operation.as_fn()).delayed_by(delay::None.take(2));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn works_as_async_operation() {
+        use try_again::retry_async;
+
+        let operation = ScriptedOperation::new([Err(Timeout), Ok("done")]);
+
+        let out = retry_async(operation.as_async_fn())
+            .with_executor(VirtualClock::new())
+            .delayed_by(delay::Fixed::of(1.secs()).take(3))
+            .await;
+
+        assert_that(out).is_ok().is_equal_to("done");
+        assert_that(operation.calls()).is_equal_to(2);
+    }
+}